@@ -0,0 +1,109 @@
+use std::{
+    fmt::{Display, Formatter},
+    iter,
+};
+
+use num_bigint::BigInt;
+
+/// The answer to a puzzle, in whatever shape the puzzle asks for.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Answer {
+    Integer(u64),
+    Signed(i64),
+    Big(BigInt),
+    String(String),
+    /// Multiple lines of text, typically letters drawn in ASCII art.
+    Grid(Vec<String>),
+}
+
+impl Answer {
+    /// A single-line rendering that fits where a number would.
+    pub fn summary(&self) -> String {
+        match self {
+            Answer::Grid(lines) => {
+                let width = lines.iter().map(|line| line.chars().count()).max();
+                format!("{}×{} grid", width.unwrap_or_default(), lines.len())
+            }
+            answer => answer.to_string(),
+        }
+    }
+
+    /// The summary, cut short with a `…` if it is longer than `width` characters, so a long answer
+    /// never passes for a shorter one.
+    pub fn summary_within(&self, width: usize) -> String {
+        let summary = self.summary();
+        if summary.chars().count() <= width {
+            return summary;
+        }
+        let kept = summary.chars().take(width.saturating_sub(1));
+        kept.chain(iter::once('…')).collect()
+    }
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Answer::Integer(n) => write!(f, "{n}"),
+            Answer::Signed(n) => write!(f, "{n}"),
+            Answer::Big(n) => write!(f, "{n}"),
+            Answer::String(s) => write!(f, "{s}"),
+            Answer::Grid(lines) => write!(f, "{}", lines.join("\n")),
+        }
+    }
+}
+
+impl From<u64> for Answer {
+    fn from(value: u64) -> Self {
+        Answer::Integer(value)
+    }
+}
+
+impl From<i64> for Answer {
+    fn from(value: i64) -> Self {
+        Answer::Signed(value)
+    }
+}
+
+impl From<u128> for Answer {
+    fn from(value: u128) -> Self {
+        u64::try_from(value).map_or_else(|_| Answer::Big(value.into()), Answer::Integer)
+    }
+}
+
+impl From<BigInt> for Answer {
+    fn from(value: BigInt) -> Self {
+        Answer::Big(value)
+    }
+}
+
+impl From<String> for Answer {
+    fn from(value: String) -> Self {
+        Answer::String(value)
+    }
+}
+
+impl From<Vec<String>> for Answer {
+    fn from(value: Vec<String>) -> Self {
+        Answer::Grid(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Answer;
+
+    #[test]
+    fn test_summary_within() {
+        assert_eq!(
+            Answer::from(u64::MAX).summary_within(20),
+            "18446744073709551615"
+        );
+        assert_eq!(
+            Answer::from(10u128.pow(20)).summary_within(20),
+            "1000000000000000000…"
+        );
+        assert_eq!(Answer::from("héllo".to_string()).summary_within(4), "hél…");
+        let grid = Answer::from(vec!["#.#".to_string(), "...".to_string()]);
+        assert_eq!(grid.summary_within(20), "3×2 grid");
+    }
+}
@@ -0,0 +1,51 @@
+use std::{collections::BTreeMap, fs, path::Path};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::{Part, input};
+
+/// Answers that were accepted, as kept in `answers.toml`, by year and day.
+#[derive(Debug, Default, Deserialize, Serialize)]
+#[serde(transparent)]
+pub struct Answers {
+    years: BTreeMap<i32, BTreeMap<i32, Recorded>>,
+}
+
+#[derive(Debug, Default, Deserialize, Serialize)]
+struct Recorded {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    one: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    two: Option<String>,
+}
+
+impl Answers {
+    /// Reads recorded answers, treating a missing file as having none.
+    pub fn load(path: &Path) -> Result<Self> {
+        input::load_or_default(path, |answers| {
+            toml::from_str(answers).with_context(|| format!("Failed to parse {}", path.display()))
+        })
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        fs::write(path, toml::to_string(self)?)
+            .with_context(|| format!("Failed to write {}", path.display()))
+    }
+
+    pub fn get(&self, year: i32, day: i32, part: Part) -> Option<&str> {
+        let recorded = self.years.get(&year)?.get(&day)?;
+        match part {
+            Part::One => recorded.one.as_deref(),
+            Part::Two => recorded.two.as_deref(),
+        }
+    }
+
+    pub fn set(&mut self, year: i32, day: i32, part: Part, answer: String) {
+        let recorded = self.years.entry(year).or_default().entry(day).or_default();
+        match part {
+            Part::One => recorded.one = Some(answer),
+            Part::Two => recorded.two = Some(answer),
+        }
+    }
+}
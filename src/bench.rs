@@ -0,0 +1,312 @@
+use std::{
+    path::Path,
+    process::ExitCode,
+    time::{Duration, Instant},
+};
+
+use anyhow::{Result, bail};
+use serde::Serialize;
+
+use crate::{
+    Part,
+    history::{Comparison, History, Origin, Run, Timing, secs},
+    input::Inputs,
+    panic,
+    solver::{self, Ctx, Output, Puzzle, Registry, Solver},
+};
+
+#[derive(Debug, clap::Args)]
+pub struct Options {
+    /// Only benchmark this day.
+    #[arg(long)]
+    day: Option<i32>,
+    /// Only benchmark this part.
+    #[arg(long)]
+    part: Option<Part>,
+    /// Number of measured runs.
+    #[arg(long, default_value_t = 10)]
+    runs: usize,
+    /// Number of unmeasured runs before those.
+    #[arg(long, default_value_t = 1)]
+    warmup: usize,
+    /// Print the results as JSON instead of a table.
+    #[arg(long)]
+    json: bool,
+    /// Don't add the results to the history.
+    #[arg(long)]
+    no_record: bool,
+}
+
+/// Timings of one part over a number of runs.
+#[derive(Debug, Serialize)]
+pub struct Benchmark {
+    pub year: i32,
+    pub day: i32,
+    pub part: Part,
+    pub runs: usize,
+    /// Time spent reading the input from disk.
+    pub read: Stats,
+    pub parse: Stats,
+    pub solve: Stats,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Stats {
+    #[serde(serialize_with = "secs::serialize")]
+    pub min: Duration,
+    #[serde(serialize_with = "secs::serialize")]
+    pub median: Duration,
+    #[serde(serialize_with = "secs::serialize")]
+    pub mean: Duration,
+    #[serde(serialize_with = "secs::serialize")]
+    pub stddev: Duration,
+}
+
+impl Stats {
+    /// Summarises a non-empty list of samples.
+    pub fn new(mut samples: Vec<Duration>) -> Self {
+        samples.sort();
+        let n = samples.len();
+        let median = if n.is_multiple_of(2) {
+            (samples[n / 2 - 1] + samples[n / 2]) / 2
+        } else {
+            samples[n / 2]
+        };
+        let mean = samples.iter().sum::<Duration>() / n as u32;
+        let variance = samples
+            .iter()
+            .map(|sample| (sample.as_secs_f64() - mean.as_secs_f64()).powi(2))
+            .sum::<f64>()
+            / n as f64;
+        Self {
+            min: samples[0],
+            median,
+            mean,
+            stddev: Duration::from_secs_f64(variance.sqrt()),
+        }
+    }
+}
+
+/// Benchmarks the selected parts and compares their median timings against the history, where
+/// `threshold` is the fraction by which a part may get slower before it counts as a regression.
+pub fn run(
+    registry: &Registry,
+    inputs: &Inputs,
+    history_path: &Path,
+    threshold: f64,
+    options: &Options,
+) -> ExitCode {
+    if options.runs == 0 {
+        eprintln!("At least one run is needed");
+        return ExitCode::FAILURE;
+    }
+    let history = match History::load(history_path, Origin::Bench) {
+        Ok(history) => history,
+        Err(error) => {
+            eprintln!("{error:#}");
+            return ExitCode::FAILURE;
+        }
+    };
+    let solvers = match registry.select(options.day) {
+        Ok(solvers) => solvers,
+        Err(error) => {
+            eprintln!("{error}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    panic::install_hook();
+    let mut failed = false;
+    let mut benchmarks = Vec::new();
+    for solver in solvers {
+        for p in [Part::One, Part::Two] {
+            if options.part.is_some_and(|part| part != p) {
+                continue;
+            }
+            match measure(
+                registry.year(),
+                solver,
+                p,
+                inputs,
+                options.runs,
+                options.warmup,
+            ) {
+                Ok(benchmark) => benchmarks.push(benchmark),
+                Err(error) => {
+                    eprintln!("Day {} part {p}: {error:#}", solver.day());
+                    failed = true;
+                }
+            }
+        }
+    }
+
+    let comparisons: Vec<_> = benchmarks
+        .iter()
+        .map(|benchmark| {
+            let (year, day, part) = (benchmark.year, benchmark.day, benchmark.part);
+            history.compare(year, day, part, benchmark.solve.median, threshold)
+        })
+        .collect();
+    if options.json {
+        println!("{}", serde_json::to_string_pretty(&benchmarks).unwrap());
+    } else {
+        print_table(&benchmarks, &comparisons);
+    }
+
+    if !options.no_record && !benchmarks.is_empty() {
+        let timings = benchmarks
+            .iter()
+            .map(|benchmark| Timing {
+                year: benchmark.year,
+                day: benchmark.day,
+                part: benchmark.part,
+                duration: benchmark.solve.median,
+            })
+            .collect();
+        if let Err(error) = History::append(history_path, &Run::new(Origin::Bench, timings)) {
+            eprintln!("{error:#}");
+            failed = true;
+        }
+    }
+
+    let regressions = comparisons.iter().filter(|comparison| comparison.regressed);
+    failed |= regressions.count() > 0;
+
+    if failed {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+/// Runs a part `warmup` times without measuring, then `runs` times with, on the current thread.
+/// `runs` must be positive.
+pub fn measure(
+    year: i32,
+    solver: &'static dyn Solver,
+    part: Part,
+    inputs: &Inputs,
+    runs: usize,
+    warmup: usize,
+) -> Result<Benchmark> {
+    let day = solver.day();
+    let mut read = Vec::with_capacity(runs);
+    let mut parse = Vec::with_capacity(runs);
+    let mut solve = Vec::with_capacity(runs);
+    for run in 0..warmup + runs {
+        let start = Instant::now();
+        let input = inputs.load(year, day)?;
+        let elapsed = start.elapsed();
+        let puzzle = Puzzle::new(solver, input);
+        let duration = match solver::run(&puzzle, part, &Ctx::default()) {
+            Output::Solved(_, duration) => duration,
+            Output::Failed(error, _) => return Err(error),
+            Output::Panicked(message) => bail!("panicked: {message}"),
+        };
+        if run >= warmup {
+            read.push(elapsed);
+            parse.extend(puzzle.parse_time());
+            solve.push(duration);
+        }
+    }
+
+    Ok(Benchmark {
+        year,
+        day,
+        part,
+        runs,
+        read: Stats::new(read),
+        parse: Stats::new(parse),
+        solve: Stats::new(solve),
+    })
+}
+
+fn print_table(benchmarks: &[Benchmark], comparisons: &[Comparison]) {
+    println!(
+        "{:>3} {:>4} {:>5} {:>10} {:>10} {:>10} {:>10} {:>10} {:>10} {:>10} {:>10} {:>8}",
+        "day",
+        "part",
+        "runs",
+        "read",
+        "parse",
+        "min",
+        "median",
+        "mean",
+        "stddev",
+        "previous",
+        "best",
+        "change"
+    );
+    for (benchmark, comparison) in benchmarks.iter().zip(comparisons) {
+        let change = comparison
+            .change(benchmark.solve.median)
+            .map_or("-".to_string(), |change| format!("{:+.1}%", change * 100.0));
+        println!(
+            "{:>3} {:>4} {:>5} {:>10} {:>10} {:>10} {:>10} {:>10} {:>10} {:>10} {:>10} {:>8}{}",
+            benchmark.day,
+            benchmark.part,
+            benchmark.runs,
+            format(benchmark.read.median),
+            format(benchmark.parse.median),
+            format(benchmark.solve.min),
+            format(benchmark.solve.median),
+            format(benchmark.solve.mean),
+            format(benchmark.solve.stddev),
+            comparison.previous.map_or("-".to_string(), format),
+            comparison.best.map_or("-".to_string(), format),
+            change,
+            if comparison.regressed {
+                " regressed"
+            } else {
+                ""
+            },
+        );
+    }
+}
+
+/// Formats a duration in the largest unit that keeps it at or above one.
+pub fn format(duration: Duration) -> String {
+    let secs = duration.as_secs_f64();
+    if secs >= 1.0 {
+        format!("{secs:.3} s")
+    } else if secs >= 1e-3 {
+        format!("{:.3} ms", secs * 1e3)
+    } else if secs >= 1e-6 {
+        format!("{:.3} µs", secs * 1e6)
+    } else {
+        format!("{} ns", duration.as_nanos())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::Stats;
+
+    fn ms(samples: &[u64]) -> Vec<Duration> {
+        samples
+            .iter()
+            .map(|&ms| Duration::from_millis(ms))
+            .collect()
+    }
+
+    #[test]
+    fn test_stats() {
+        let stats = Stats::new(ms(&[3, 1, 4, 2]));
+        assert_eq!(stats.min, Duration::from_millis(1));
+        assert_eq!(stats.median, Duration::from_micros(2500));
+        assert_eq!(stats.mean, Duration::from_micros(2500));
+        assert!((stats.stddev.as_secs_f64() - 1.25f64.sqrt() / 1000.0).abs() < 1e-9);
+
+        let stats = Stats::new(ms(&[9, 1, 5]));
+        assert_eq!(stats.median, Duration::from_millis(5));
+        assert_eq!(stats.mean, Duration::from_millis(5));
+        assert!((stats.stddev.as_secs_f64() - (32f64 / 3.0).sqrt() / 1000.0).abs() < 1e-9);
+
+        let stats = Stats::new(ms(&[7]));
+        let seven = Duration::from_millis(7);
+        assert_eq!((stats.min, stats.median, stats.mean), (seven, seven, seven));
+        assert_eq!(stats.stddev, Duration::ZERO);
+    }
+}
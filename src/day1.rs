@@ -1,32 +1,79 @@
-use anyhow::{Result, anyhow};
+use anyhow::{Context, Result, anyhow, bail};
 use std::{
     iter::{self, RepeatN},
     str::FromStr,
 };
 
-pub fn solve_a(input: &str) -> u64 {
-    input
-        .lines()
-        .map(Rotation::from_str)
-        .map(Result::unwrap)
+use crate::{
+    answer::Answer,
+    solver::{Ctx, Day, Example},
+};
+
+const EXAMPLES: &[Example] = &[Example {
+    input: "L68\nL30\nR48\nL5\nR60\nL55\nL1\nL99\nR14\nL82\n",
+    part_one: Some("3"),
+    part_two: Some("6"),
+}];
+
+pub struct Day1;
+
+impl Day for Day1 {
+    type Input = Vec<Rotation>;
+
+    fn day(&self) -> i32 {
+        1
+    }
+
+    fn title(&self) -> &'static str {
+        "Secret Entrance"
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        EXAMPLES
+    }
+
+    fn parse(&self, input: &str) -> Result<Self::Input> {
+        parse(input)
+    }
+
+    fn solve_a(&self, input: &Self::Input, _ctx: &Ctx) -> Result<Answer> {
+        Ok(solve_a(input)?.into())
+    }
+
+    fn solve_b(&self, input: &Self::Input, ctx: &Ctx) -> Result<Answer> {
+        Ok(solve_b(input, ctx)?.into())
+    }
+}
+
+fn solve_a(rotations: &[Rotation]) -> Result<u64> {
+    Ok(rotations
+        .iter()
+        .cloned()
         .scan(50, rotate)
         .filter(|&x| x == 0)
         .count()
-        .try_into()
-        .unwrap()
+        .try_into()?)
 }
 
-pub fn solve_b(input: &str) -> u64 {
+fn solve_b(rotations: &[Rotation], ctx: &Ctx) -> Result<u64> {
+    let progress = ctx.progress(rotations.len() as u64);
+    let mut number = 50;
+    let mut zeros = 0;
+    for rotation in rotations.iter().cloned() {
+        ctx.check()?;
+        progress.advance(1);
+        zeros += rotation.zeros(number);
+        rotate(&mut number, rotation);
+    }
+    Ok(zeros)
+}
+
+fn parse(input: &str) -> Result<Vec<Rotation>> {
     input
         .lines()
-        .map(Rotation::from_str)
-        .map(Result::unwrap)
-        .flatten()
-        .scan(50, rotate)
-        .filter(|&x| x == 0)
-        .count()
-        .try_into()
-        .unwrap()
+        .enumerate()
+        .map(|(index, line)| line.parse().with_context(|| format!("Line {}", index + 1)))
+        .collect()
 }
 
 fn rotate(number: &mut i64, rotation: Rotation) -> Option<i64> {
@@ -34,15 +81,32 @@ fn rotate(number: &mut i64, rotation: Rotation) -> Option<i64> {
     Some(*number)
 }
 
-#[derive(Clone)]
-struct Rotation(i64);
+#[derive(Clone, Debug)]
+pub struct Rotation(i64);
+
+impl Rotation {
+    /// How often the dial passes or lands on zero when rotating from `number`, which is the number
+    /// of multiples of 100 among the ones it clicks to.
+    fn zeros(&self, number: i64) -> u64 {
+        let (first, last) = if self.0 >= 0 {
+            (number + 1, number + self.0)
+        } else {
+            (number + self.0, number - 1)
+        };
+        (last.div_euclid(100) - (first - 1).div_euclid(100)) as u64
+    }
+}
 
 impl FromStr for Rotation {
     type Err = anyhow::Error;
 
     fn from_str(s: &str) -> Result<Self> {
-        let (direction, distance) = s.split_at(1);
-        let distance = distance.parse()?;
+        let Some((direction, distance)) = s.split_at_checked(1) else {
+            bail!("Expected a direction and a distance, got {s:?}");
+        };
+        let distance: i64 = distance
+            .parse()
+            .with_context(|| format!("Failed to parse distance of {s}"))?;
         match direction {
             "R" => Ok(Rotation(distance)),
             "L" => Ok(Rotation(-distance)),
@@ -51,6 +115,7 @@ impl FromStr for Rotation {
     }
 }
 
+/// Single clicks in the direction of the rotation.
 impl IntoIterator for Rotation {
     type Item = Rotation;
 
@@ -60,3 +125,48 @@ impl IntoIterator for Rotation {
         iter::repeat_n(Rotation(self.0.signum()), self.0.abs().try_into().unwrap())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{EXAMPLES, Rotation, parse, rotate};
+    use crate::solver::Ctx;
+
+    #[test]
+    fn test_b() {
+        let rotations = parse(EXAMPLES[0].input).unwrap();
+        assert_eq!(super::solve_b(&rotations, &Ctx::default()).unwrap(), 6);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let error = parse("R5\n\nL3\n").unwrap_err();
+        assert_eq!(
+            format!("{error:#}"),
+            "Line 2: Expected a direction and a distance, got \"\""
+        );
+        let error = parse("R5\nLx\n").unwrap_err();
+        assert_eq!(
+            format!("{error:#}"),
+            "Line 2: Failed to parse distance of Lx: invalid digit found in string"
+        );
+    }
+
+    #[test]
+    fn test_zeros_matches_clicks() {
+        for start in 0..100 {
+            for distance in -350..=350 {
+                let mut number = start;
+                let clicks = Rotation(distance)
+                    .into_iter()
+                    .filter_map(|click| rotate(&mut number, click))
+                    .filter(|&x| x == 0)
+                    .count();
+                assert_eq!(
+                    Rotation(distance).zeros(start),
+                    clicks as u64,
+                    "rotating {distance} from {start}"
+                );
+            }
+        }
+    }
+}
@@ -1,98 +1,226 @@
-use std::{collections::BTreeSet, str::FromStr};
+use anyhow::{Context, Result};
 
-use anyhow::{Context, Error, Result, anyhow};
+use crate::{
+    answer::Answer,
+    intervals::RangeSet,
+    solver::{Ctx, Day, Example},
+};
 
-pub fn solve_a(input: &str) -> u64 {
-    parse_input(input)
-        .unwrap()
-        .iter()
-        .flat_map(Range::invalid)
-        .sum()
-}
+const EXAMPLES: &[Example] = &[Example {
+    input: "11-22,95-115,998-1012,1188511880-1188511890,222220-222224,1698522-1698528,446443-446449,38593856-38593862,565653-565659,824824821-824824827,2121212118-2121212124",
+    part_one: Some("1227775554"),
+    part_two: Some("4174379265"),
+}];
 
-pub fn solve_b(input: &str) -> u64 {
-    parse_input(input)
-        .unwrap()
-        .iter()
-        .flat_map(Range::also_invalid)
-        .sum()
-}
+pub struct Day2;
 
-fn parse_input(input: &str) -> Result<Vec<Range>> {
-    input.trim().split(',').map(Range::from_str).collect()
-}
+impl Day for Day2 {
+    type Input = RangeSet<u128>;
 
-struct Range {
-    from: u64,
-    to: u64,
-}
+    fn day(&self) -> i32 {
+        2
+    }
 
-impl Range {
-    fn invalid(&self) -> impl Iterator<Item = u64> {
-        self.invalid_n(2)
+    fn title(&self) -> &'static str {
+        "Gift Shop"
     }
 
-    fn also_invalid(&self) -> BTreeSet<u64> {
-        [2, 3, 5, 7]
-            .iter()
-            .flat_map(|&n| self.invalid_n(n))
-            .collect()
+    fn examples(&self) -> &'static [Example] {
+        EXAMPLES
     }
 
-    fn invalid_n(&self, n: u32) -> impl Iterator<Item = u64> {
-        let from = first_nth(self.from, n);
-        let to = first_nth(self.to, n) + 1;
-        (from..to)
-            .map(move |x| concat_n(x, n))
-            .skip_while(|&x| !self.contains(x))
-            .take_while(|&x| self.contains(x))
+    fn parse(&self, input: &str) -> Result<Self::Input> {
+        input.parse()
     }
 
-    fn contains(&self, n: u64) -> bool {
-        self.from <= n && self.to >= n
+    fn solve_a(&self, input: &Self::Input, ctx: &Ctx) -> Result<Answer> {
+        Ok(solve_a(input, ctx)?.into())
+    }
+
+    fn solve_b(&self, input: &Self::Input, ctx: &Ctx) -> Result<Answer> {
+        Ok(solve_b(input, ctx)?.into())
     }
 }
 
-fn first_nth(x: u64, n: u32) -> u64 {
-    let log = x.ilog10();
-    if (log + 1) % n == 0 {
-        x / 10u64.pow((log + 1) - (log + 1) / n)
-    } else {
-        10u64.pow(log / n)
+fn solve_a(ranges: &RangeSet<u128>, ctx: &Ctx) -> Result<u128> {
+    let progress = ctx.progress(ranges.ranges().len() as u64);
+    ranges
+        .iter()
+        .inspect(|_| progress.advance(1))
+        .map(|range| repeated_sum(range.start, range.end, 2))
+        .try_fold(0, checked_add)
+}
+
+fn solve_b(ranges: &RangeSet<u128>, ctx: &Ctx) -> Result<u128> {
+    let progress = ctx.progress(ranges.ranges().len() as u64);
+    ranges
+        .iter()
+        .inspect(|_| progress.advance(1))
+        .map(|range| any_repeated_sum(range.start, range.end))
+        .try_fold(0, checked_add)
+}
+
+/// Sum of the numbers in `from..=to` that are a block of digits repeated any number of times.
+///
+/// A number made of blocks repeated `k` times is also made of blocks repeated `j` times for every
+/// `j` dividing `k`, so summing over all `k` counts some numbers several times. Weighting each sum
+/// by the negated Möbius function of `k` counts each of them exactly once.
+fn any_repeated_sum(from: u128, to: u128) -> Result<u128> {
+    let (mut added, mut subtracted) = (0, 0);
+    for times in 2..=digits(to) {
+        match mobius(times) {
+            -1 => added = checked_add(added, repeated_sum(from, to, times))?,
+            1 => subtracted = checked_add(subtracted, repeated_sum(from, to, times))?,
+            _ => {}
+        }
     }
+    Ok(added - subtracted)
 }
 
-fn concat_n(x: u64, n: u32) -> u64 {
-    let mut acc = 0u64;
-    let mul = 10u64.pow(x.ilog10() + 1);
-    for _ in 0..n {
-        acc = acc * mul + x;
+/// Sum of the numbers in `from..=to` that are a block of digits repeated exactly `times` times.
+///
+/// For each length, such numbers are the blocks multiplied by a number like `1001001`, so they
+/// form an arithmetic series.
+fn repeated_sum(from: u128, to: u128, times: u32) -> Result<u128> {
+    let mut sum = 0;
+    for len in (times..=digits(to)).step_by(times as usize) {
+        let block = len / times;
+        let Some(unit) = repunit(block, times) else {
+            break;
+        };
+        // Blocks can't start with a zero, which also keeps the numbers `len` digits long.
+        let first = from.div_ceil(unit).max(10u128.pow(block - 1));
+        let last = (to / unit).min(10u128.pow(block) - 1);
+        if first <= last {
+            let series = series(first, last).context("Sum of blocks is too large")?;
+            let product = series.checked_mul(unit).context("Sum is too large")?;
+            sum = checked_add(sum, Ok(product))?;
+        }
     }
-    acc
+    Ok(sum)
 }
 
-impl FromStr for Range {
-    type Err = Error;
+/// `1`, followed by `times - 1` copies of `block - 1` zeros and a one, if that fits.
+fn repunit(block: u32, times: u32) -> Option<u128> {
+    let shift = 10u128.checked_pow(block)?;
+    (1..times).try_fold(1u128, |unit, _| unit.checked_mul(shift)?.checked_add(1))
+}
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (from, to) = s.split_once('-').ok_or(anyhow!("No hyphen in {s}"))?;
-        let from = from
-            .parse()
-            .with_context(|| format!("Failed to parse start of range {s}"))?;
-        let to = to
-            .parse()
-            .with_context(|| format!("Failed to parse end of range {s}"))?;
+/// Sum of `first..=last`.
+fn series(first: u128, last: u128) -> Option<u128> {
+    let (count, total) = (last - first + 1, first.checked_add(last)?);
+    if count.is_multiple_of(2) {
+        (count / 2).checked_mul(total)
+    } else {
+        count.checked_mul(total / 2)
+    }
+}
 
-        Ok(Range { from, to })
+fn digits(n: u128) -> u32 {
+    n.checked_ilog10().map_or(1, |log| log + 1)
+}
+
+fn mobius(mut n: u32) -> i32 {
+    let mut result = 1;
+    let mut factor = 2;
+    while factor * factor <= n {
+        if n.is_multiple_of(factor) {
+            n /= factor;
+            if n.is_multiple_of(factor) {
+                return 0;
+            }
+            result = -result;
+        }
+        factor += 1;
     }
+    if n > 1 { -result } else { result }
+}
+
+fn checked_add(sum: u128, n: Result<u128>) -> Result<u128> {
+    sum.checked_add(n?).context("Sum is too large")
 }
 
 #[cfg(test)]
 mod tests {
-    const INPUT: &str = "11-22,95-115,998-1012,1188511880-1188511890,222220-222224,1698522-1698528,446443-446449,38593856-38593862,565653-565659,824824821-824824827,2121212118-2121212124";
+    use super::{EXAMPLES, any_repeated_sum, repeated_sum};
+    use crate::solver::Ctx;
+
+    /// Whether `n` is a block of digits repeated `times` times, or any number of times if `None`.
+    fn is_repeated(n: u128, times: Option<usize>) -> bool {
+        let digits = n.to_string();
+        (2..=digits.len())
+            .filter(|&k| times.is_none_or(|times| times == k))
+            .filter(|&k| digits.len().is_multiple_of(k))
+            .any(|k| digits == digits[..digits.len() / k].repeat(k))
+    }
+
+    #[test]
+    fn test_a() {
+        assert_eq!(
+            super::solve_a(&EXAMPLES[0].input.parse().unwrap(), &Ctx::default()).unwrap(),
+            1227775554
+        )
+    }
 
     #[test]
     fn test_b() {
-        assert_eq!(super::solve_b(INPUT), 4174379265)
+        assert_eq!(
+            super::solve_b(&EXAMPLES[0].input.parse().unwrap(), &Ctx::default()).unwrap(),
+            4174379265
+        )
+    }
+
+    #[test]
+    fn test_parse_wide_ranges() {
+        // 22 digits, which is too many for a `u64`.
+        let ranges = "1234567890112345678901-1234567890112345678901"
+            .parse()
+            .unwrap();
+        assert_eq!(
+            super::solve_a(&ranges, &Ctx::default()).unwrap(),
+            1234567890112345678901
+        );
+        assert_eq!(
+            super::solve_b(&ranges, &Ctx::default()).unwrap(),
+            1234567890112345678901
+        );
+    }
+
+    #[test]
+    fn test_matches_brute_force() {
+        for (from, to) in [(0, 1), (1, 9), (5, 120), (1, 250_000), (998_990, 1_012_000)] {
+            let brute = |times| (from..=to).filter(|&n| is_repeated(n, times)).sum::<u128>();
+            assert_eq!(repeated_sum(from, to, 2).unwrap(), brute(Some(2)));
+            assert_eq!(repeated_sum(from, to, 3).unwrap(), brute(Some(3)));
+            assert_eq!(any_repeated_sum(from, to).unwrap(), brute(None));
+        }
+    }
+
+    #[test]
+    fn test_wide_ranges() {
+        // 38 digits, so only the widest numbers `u128` holds.
+        let n = 1234567890123456789_1234567890123456789;
+        assert_eq!(repeated_sum(n, n, 2).unwrap(), n);
+        assert_eq!(any_repeated_sum(n, n).unwrap(), n);
+        assert_eq!(any_repeated_sum(n + 1, n + 1).unwrap(), 0);
+        let all_ones = 11111111111111111111111111111111111111;
+        assert_eq!(any_repeated_sum(all_ones, all_ones).unwrap(), all_ones);
+
+        // Sums over wide ranges add up like sums over their parts.
+        let (from, split, to) = (1, 123_456_789_012_345_678, 10u128.pow(24));
+        assert_eq!(
+            any_repeated_sum(from, to).unwrap(),
+            any_repeated_sum(from, split).unwrap() + any_repeated_sum(split + 1, to).unwrap()
+        );
+        // Every 24 digit number made of 12 digit blocks.
+        let low = 10u128.pow(24) / 10;
+        let count = 9 * 10u128.pow(11);
+        let blocks = (10u128.pow(11) + 10u128.pow(12) - 1) * count / 2;
+        assert_eq!(
+            repeated_sum(low, 10u128.pow(24) - 1, 2).unwrap(),
+            blocks * (10u128.pow(12) + 1)
+        );
+
+        assert!(any_repeated_sum(1, u128::MAX).is_err());
     }
 }
@@ -1,25 +1,152 @@
-pub fn solve_a(input: &str) -> u64 {
-    parse(input).map(|bank| solve(&bank, 1)).sum()
+use anyhow::{Result, bail};
+use num_bigint::{BigInt, Sign};
+
+use crate::{
+    answer::Answer,
+    solver::{Ctx, Day, Example},
+};
+
+const EXAMPLES: &[Example] = &[Example {
+    input: "987654321111111\n811111111111119\n234234234234278\n818181911112111\n",
+    part_one: Some("357"),
+    part_two: Some("3121910778619"),
+}];
+
+pub struct Day3;
+
+impl Day for Day3 {
+    type Input = Vec<Bank>;
+
+    fn day(&self) -> i32 {
+        3
+    }
+
+    fn title(&self) -> &'static str {
+        "Lobby"
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        EXAMPLES
+    }
+
+    fn parse(&self, input: &str) -> Result<Self::Input> {
+        parse(input)
+    }
+
+    fn solve_a(&self, input: &Self::Input, _ctx: &Ctx) -> Result<Answer> {
+        solve(input, 2)
+    }
+
+    fn solve_b(&self, input: &Self::Input, _ctx: &Ctx) -> Result<Answer> {
+        solve(input, 12)
+    }
+}
+
+pub struct Bank {
+    /// Line of the input the bank is on, counting from one.
+    line: usize,
+    batteries: Vec<u8>,
 }
 
-pub fn solve_b(input: &str) -> u64 {
-    parse(input).map(|bank| solve(&bank, 11)).sum()
+/// Sums the largest joltage of each bank when turning on `count` batteries, which may not fit in a
+/// `u128` for long selections.
+fn solve(banks: &[Bank], count: usize) -> Result<Answer> {
+    let mut total = BigInt::ZERO;
+    for bank in banks {
+        total += BigInt::from_radix_be(Sign::Plus, &joltage(bank, count)?, 10).unwrap_or_default();
+    }
+    Ok(u128::try_from(&total).map_or(Answer::Big(total), Answer::from))
 }
 
-fn parse(input: &str) -> impl Iterator<Item = Vec<u64>> {
-    input.lines().map(|line| {
-        line.chars()
-            .map(|c| c.to_digit(10).unwrap() as u64)
-            .collect()
-    })
+fn parse(input: &str) -> Result<Vec<Bank>> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(index, line)| {
+            let batteries = line
+                .chars()
+                .map(|c| match c.to_digit(10) {
+                    Some(digit) => Ok(digit as u8),
+                    None => bail!("Line {}: {c:?} is not a battery", index + 1),
+                })
+                .collect::<Result<_>>()?;
+            Ok(Bank {
+                line: index + 1,
+                batteries,
+            })
+        })
+        .collect()
+}
+
+/// The digits of the largest number made of `count` batteries of a bank, in order.
+///
+/// Going through the batteries once, a battery replaces the ones before it that are smaller, as
+/// long as enough batteries are left to make up `count`.
+fn joltage(bank: &Bank, count: usize) -> Result<Vec<u8>> {
+    let Bank { line, batteries } = bank;
+    let Some(mut skips) = batteries.len().checked_sub(count) else {
+        bail!(
+            "Line {line}: {} batteries are fewer than {count}",
+            batteries.len()
+        );
+    };
+    let mut selected = Vec::with_capacity(batteries.len());
+    for &battery in batteries {
+        while skips > 0
+            && let Some(&last) = selected.last()
+            && last < battery
+        {
+            selected.pop();
+            skips -= 1;
+        }
+        selected.push(battery);
+    }
+    selected.truncate(count);
+    Ok(selected)
 }
 
-fn solve(bank: &[u64], left: usize) -> u64 {
-    let max = bank[..bank.len() - left].iter().max().unwrap();
-    let index = bank.iter().position(|&x| x == *max).unwrap();
-    if left == 0 {
-        *max
-    } else {
-        10u64.pow(left as u32) * max + solve(&bank[index + 1..], left - 1)
+#[cfg(test)]
+mod tests {
+    use super::{EXAMPLES, joltage, parse};
+    use crate::answer::Answer;
+
+    #[test]
+    fn test_joltage() {
+        let banks = parse(EXAMPLES[0].input).unwrap();
+        assert_eq!(joltage(&banks[0], 2).unwrap(), [9, 8]);
+        assert_eq!(joltage(&banks[1], 2).unwrap(), [8, 9]);
+        assert_eq!(
+            joltage(&banks[3], 12).unwrap(),
+            [8, 8, 8, 9, 1, 1, 1, 1, 2, 1, 1, 1]
+        );
+        assert_eq!(joltage(&banks[2], 15).unwrap(), banks[2].batteries);
+        assert!(joltage(&banks[2], 0).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_b() {
+        let banks = parse(EXAMPLES[0].input).unwrap();
+        assert_eq!(
+            super::solve(&banks, 12).unwrap(),
+            Answer::Integer(3121910778619)
+        );
+    }
+
+    #[test]
+    fn test_long_selection() {
+        let banks = parse(&"9".repeat(50)).unwrap();
+        assert_eq!(
+            super::solve(&banks, 45).unwrap().to_string(),
+            "9".repeat(45)
+        );
+    }
+
+    #[test]
+    fn test_errors() {
+        let error = parse("12\n3x4\n").err().unwrap();
+        assert_eq!(error.to_string(), "Line 2: 'x' is not a battery");
+        let banks = parse("1234\n123\n").unwrap();
+        let error = super::solve(&banks, 4).unwrap_err();
+        assert_eq!(error.to_string(), "Line 2: 3 batteries are fewer than 4");
     }
 }
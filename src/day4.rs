@@ -1,7 +1,124 @@
-pub fn solve_a(input: &str) -> u64 {
-    0
+use anyhow::{Result, bail};
+
+use crate::{
+    answer::Answer,
+    grid::{Grid, Pos},
+    solver::{Ctx, Day, Example},
+};
+
+const EXAMPLES: &[Example] = &[Example {
+    input: "..@@.@@@@.\n@@@.@.@.@@\n@@@@@.@.@@\n@.@@@@..@.\n@@.@@@@.@@\n.@@@@@@@.@\n.@.@.@.@@@\n@.@@@.@@@@\n.@@@@@@@@.\n@.@.@@@.@.\n",
+    part_one: Some("13"),
+    part_two: Some("43"),
+}];
+
+/// A roll can be reached by a forklift if fewer than this many rolls are around it.
+const CROWDED: usize = 4;
+
+pub struct Day4;
+
+impl Day for Day4 {
+    /// Where the rolls of paper are.
+    type Input = Grid<bool>;
+
+    fn day(&self) -> i32 {
+        4
+    }
+
+    fn title(&self) -> &'static str {
+        "Printing Department"
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        EXAMPLES
+    }
+
+    fn parse(&self, input: &str) -> Result<Self::Input> {
+        Grid::parse_with(input, |c| match c {
+            '@' => Ok(true),
+            '.' => Ok(false),
+            _ => bail!("Unexpected {c:?}"),
+        })
+    }
+
+    fn solve_a(&self, input: &Self::Input, _ctx: &Ctx) -> Result<Answer> {
+        Ok(solve_a(input)?.into())
+    }
+
+    fn solve_b(&self, input: &Self::Input, _ctx: &Ctx) -> Result<Answer> {
+        Ok(solve_b(input)?.into())
+    }
 }
 
-pub fn solve_b(input: &str) -> u64 {
-    0
+fn solve_a(rolls: &Grid<bool>) -> Result<u64> {
+    Ok(rolls
+        .positions()
+        .filter(|&pos| rolls[pos] && neighbours(rolls, pos) < CROWDED)
+        .count()
+        .try_into()?)
+}
+
+/// Removes reachable rolls until none are left. Removing a roll only changes whether its
+/// neighbours are reachable, so those are the only ones to look at again.
+fn solve_b(rolls: &Grid<bool>) -> Result<u64> {
+    let mut rolls = rolls.clone();
+    let mut counts = rolls.map(|_| 0);
+    for pos in rolls.positions() {
+        counts[pos] = neighbours(&rolls, pos);
+    }
+    let mut reachable: Vec<_> = rolls
+        .positions()
+        .filter(|&pos| rolls[pos] && counts[pos] < CROWDED)
+        .collect();
+
+    let mut removed = 0;
+    while let Some(pos) = reachable.pop() {
+        if !rolls[pos] {
+            continue;
+        }
+        rolls[pos] = false;
+        removed += 1;
+        for neighbour in rolls.neighbours8(pos) {
+            counts[neighbour] -= 1;
+            // Rolls that were reachable before are queued already.
+            if rolls[neighbour] && counts[neighbour] == CROWDED - 1 {
+                reachable.push(neighbour);
+            }
+        }
+    }
+    Ok(removed)
+}
+
+/// Number of rolls around `pos`.
+fn neighbours(rolls: &Grid<bool>, pos: Pos) -> usize {
+    rolls
+        .neighbours8(pos)
+        .filter(|&neighbour| rolls[neighbour])
+        .count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Day4, EXAMPLES};
+    use crate::solver::Day;
+
+    #[test]
+    fn test_a() {
+        let rolls = Day4.parse(EXAMPLES[0].input).unwrap();
+        assert_eq!(super::solve_a(&rolls).unwrap(), 13);
+    }
+
+    #[test]
+    fn test_b() {
+        let rolls = Day4.parse(EXAMPLES[0].input).unwrap();
+        assert_eq!(super::solve_b(&rolls).unwrap(), 43);
+    }
+
+    #[test]
+    fn test_b_removes_in_waves() {
+        // Only the corners are reachable at first, then the edges, then the middle.
+        let rolls = Day4.parse("@@@\n@@@\n@@@\n").unwrap();
+        assert_eq!(super::solve_a(&rolls).unwrap(), 4);
+        assert_eq!(super::solve_b(&rolls).unwrap(), 9);
+    }
 }
@@ -1,91 +1,70 @@
-use std::str::FromStr;
+use anyhow::{Context, Result, anyhow};
 
-use anyhow::{Context, Error, anyhow};
+use crate::{
+    answer::Answer,
+    intervals::RangeSet,
+    solver::{Ctx, Day, Example},
+};
 
-pub fn solve_a(input: &str) -> u64 {
-    let (ranges, ids) = parse(input);
-    ids.into_iter()
-        .filter(|&id| ranges.iter().any(|range| range.contains(id)))
-        .count()
-        .try_into()
-        .unwrap()
-}
+const EXAMPLES: &[Example] = &[Example {
+    input: "3-5\n10-14\n16-20\n12-18\n\n1\n5\n8\n11\n17\n32\n",
+    part_one: Some("3"),
+    part_two: Some("14"),
+}];
 
-pub fn solve_b(input: &str) -> u64 {
-    let (mut ranges, _) = parse(input);
-    ranges.sort_by(|a, b| a.from.cmp(&b.from));
-    ranges
-        .into_iter()
-        .fold(Vec::<Range>::new(), |mut acc, e| {
-            let mut joined = false;
-            for range in acc.iter_mut() {
-                if range.overlaps(&e) {
-                    range.join_unchecked(&e);
-                    joined = true;
-                }
-            }
-            if !joined {
-                acc.push(e);
-            }
-            acc
-        })
-        .into_iter()
-        .map(|range| range.len())
-        .sum()
-}
+pub struct Day5;
 
-fn parse(input: &str) -> (Vec<Range>, Vec<u64>) {
-    let (ranges, ids) = input.split_once("\n\n").unwrap();
-    let ranges = ranges
-        .lines()
-        .map(Range::from_str)
-        .map(Result::unwrap)
-        .collect();
-    let ids = ids.lines().map(|id| id.parse().unwrap()).collect();
-    (ranges, ids)
-}
+impl Day for Day5 {
+    type Input = (RangeSet, Vec<u64>);
 
-struct Range {
-    from: u64,
-    to: u64,
-}
+    fn day(&self) -> i32 {
+        5
+    }
 
-impl Range {
-    fn len(self) -> u64 {
-        self.to - self.from + 1
+    fn title(&self) -> &'static str {
+        "Cafeteria"
     }
 
-    fn contains(&self, n: u64) -> bool {
-        n >= self.from && n <= self.to
+    fn examples(&self) -> &'static [Example] {
+        EXAMPLES
     }
 
-    fn overlaps(&self, other: &Self) -> bool {
-        if self.from <= other.from && self.to >= other.to
-            || other.from <= self.from && other.to >= self.to
-        {
-            true
-        } else {
-            self.from.max(other.from) <= self.to.min(other.to)
-        }
+    fn parse(&self, input: &str) -> Result<Self::Input> {
+        parse(input)
     }
 
-    fn join_unchecked(&mut self, other: &Self) {
-        self.from = self.from.min(other.from);
-        self.to = self.to.max(other.to);
+    fn solve_a(&self, input: &Self::Input, _ctx: &Ctx) -> Result<Answer> {
+        Ok(solve_a(input)?.into())
+    }
+
+    fn solve_b(&self, input: &Self::Input, _ctx: &Ctx) -> Result<Answer> {
+        Ok(solve_b(input)?.into())
     }
 }
 
-impl FromStr for Range {
-    type Err = Error;
+fn solve_a((ranges, ids): &(RangeSet, Vec<u64>)) -> Result<u64> {
+    Ok(ids
+        .iter()
+        .filter(|&&id| ranges.contains(id))
+        .count()
+        .try_into()?)
+}
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (from, to) = s.split_once('-').ok_or(anyhow!("No hyphen in {s}"))?;
-        let from = from
-            .parse()
-            .with_context(|| format!("Failed to parse range start of {s}"))?;
-        let to = to
-            .parse()
-            .with_context(|| format!("Failed to parse range end of {s}"))?;
-        Ok(Range { from, to })
-    }
+fn solve_b((ranges, _): &(RangeSet, Vec<u64>)) -> Result<u128> {
+    ranges.len().context("Too many fresh ids to count")
+}
+
+fn parse(input: &str) -> Result<(RangeSet, Vec<u64>)> {
+    let (ranges, ids) = input
+        .split_once("\n\n")
+        .ok_or(anyhow!("No blank line between ranges and ids"))?;
+    let ranges = ranges.parse()?;
+    let ids = ids
+        .lines()
+        .map(|id| {
+            id.parse()
+                .with_context(|| format!("Failed to parse id {id}"))
+        })
+        .collect::<Result<_>>()?;
+    Ok((ranges, ids))
 }
@@ -1,63 +1,106 @@
 use std::{iter, str::FromStr};
 
-use anyhow::{Error, anyhow};
+use anyhow::{Context, Error, Result, anyhow};
 use itertools::Itertools;
 
-pub fn solve_a(input: &str) -> u64 {
-    let matrix: Vec<Vec<_>> = input
-        .lines()
+use crate::{
+    answer::Answer,
+    grid::Grid,
+    solver::{Ctx, Day, Example},
+};
+
+const EXAMPLES: &[Example] = &[Example {
+    input: "123 328  51 64 \n 45 64  387 23 \n  6 98  215 314\n*   +   *   +  \n",
+    part_one: Some("4277556"),
+    part_two: Some("3263827"),
+}];
+
+pub struct Day6;
+
+impl Day for Day6 {
+    type Input = Vec<String>;
+
+    fn day(&self) -> i32 {
+        6
+    }
+
+    fn title(&self) -> &'static str {
+        "Trash Compactor"
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        EXAMPLES
+    }
+
+    fn parse(&self, input: &str) -> Result<Self::Input> {
+        Ok(input.lines().map(str::to_string).collect())
+    }
+
+    fn solve_a(&self, input: &Self::Input, _ctx: &Ctx) -> Result<Answer> {
+        Ok(solve_a(input)?.into())
+    }
+
+    fn solve_b(&self, input: &Self::Input, _ctx: &Ctx) -> Result<Answer> {
+        Ok(solve_b(input)?.into())
+    }
+}
+
+fn solve_a(lines: &[String]) -> Result<u64> {
+    let matrix: Vec<Vec<_>> = lines
+        .iter()
         .map(|line| line.split_whitespace().collect())
         .collect();
 
-    transpose(matrix)
-        .into_iter()
-        .map(|parts| Expr::from_parts(&parts))
-        .map(Expr::eval)
+    Grid::from_rows(matrix)?
+        .transpose()
+        .rows()
+        .map(Expr::from_parts)
+        .map_ok(Expr::eval)
         .sum()
 }
 
-pub fn solve_b(input: &str) -> u64 {
-    let ops_line = input.lines().last().unwrap();
-    let matrix = input
-        .lines()
-        .take(4)
+fn solve_b(lines: &[String]) -> Result<u64> {
+    let (ops_line, number_lines) = lines.split_last().context("Empty input")?;
+    let matrix = number_lines
+        .iter()
+        .map(String::as_str)
         .chain(iter::once(
             &*iter::repeat_n(' ', ops_line.len()).collect::<String>(),
         ))
-        .chain(iter::once(ops_line))
+        .chain(iter::once(ops_line.as_str()))
         .map(|line| line.chars().collect())
         .collect();
 
-    transpose(matrix)
-        .into_iter()
-        .map(|line| line.into_iter().collect::<String>())
+    Grid::from_rows(matrix)?
+        .transpose()
+        .rows()
+        .map(|line| line.iter().collect::<String>())
         .batching(|it| {
             let mut op = None;
             let mut nums = Vec::new();
-            while let Some((num, o)) = batching_line(it) {
+            while let Some(line) = batching_line(it) {
+                let (num, o) = match line {
+                    Ok(line) => line,
+                    Err(error) => return Some(Err(error)),
+                };
                 nums.push(num);
                 op = op.or(o);
             }
-            Some(Expr { nums, op: op? })
+            Some(Ok(Expr { nums, op: op? }))
         })
-        .map(Expr::eval)
+        .map_ok(Expr::eval)
         .sum()
 }
 
-fn transpose<T: Copy>(matrix: Vec<Vec<T>>) -> Vec<Vec<T>> {
-    let rows = matrix.len();
-    let cols = matrix[0].len();
-    (0..cols)
-        .map(|col| (0..rows).map(|row| matrix[row][col]).collect())
-        .collect()
-}
-
-fn batching_line(it: &mut impl Iterator<Item = String>) -> Option<(u64, Option<Op>)> {
+fn batching_line(it: &mut impl Iterator<Item = String>) -> Option<Result<(u64, Option<Op>)>> {
     let line = it.next()?;
     let mut parts = line.split_whitespace();
-    let num = parts.next()?.parse().unwrap();
-    let op = parts.next().map(Op::from_str).transpose().unwrap();
-    Some((num, op))
+    let num = parts.next()?;
+    let num = num
+        .parse()
+        .with_context(|| format!("Failed to parse number {num}"));
+    let op = parts.next().map(Op::from_str).transpose();
+    Some(num.and_then(|num| Ok((num, op?))))
 }
 
 struct Expr {
@@ -66,13 +109,17 @@ struct Expr {
 }
 
 impl Expr {
-    fn from_parts(parts: &[&str]) -> Self {
-        let nums = parts[0..parts.len() - 1]
+    fn from_parts(parts: &[&str]) -> Result<Self> {
+        let (op, nums) = parts.split_last().ok_or(anyhow!("Empty problem"))?;
+        let nums = nums
             .iter()
-            .map(|n| n.parse().unwrap())
-            .collect();
-        let op = parts.last().unwrap().parse().unwrap();
-        Expr { nums, op }
+            .map(|n| {
+                n.parse()
+                    .with_context(|| format!("Failed to parse number {n}"))
+            })
+            .collect::<Result<_>>()?;
+        let op = op.parse()?;
+        Ok(Expr { nums, op })
     }
 
     fn eval(self) -> u64 {
@@ -99,3 +146,22 @@ impl FromStr for Op {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::EXAMPLES;
+
+    fn lines() -> Vec<String> {
+        EXAMPLES[0].input.lines().map(str::to_string).collect()
+    }
+
+    #[test]
+    fn test_a() {
+        assert_eq!(super::solve_a(&lines()).unwrap(), 4277556);
+    }
+
+    #[test]
+    fn test_b() {
+        assert_eq!(super::solve_b(&lines()).unwrap(), 3263827);
+    }
+}
@@ -1,44 +1,18 @@
-use std::collections::BTreeSet;
-
-pub fn solve_a(input: &str) -> u64 {
-    input
-        .lines()
-        .enumerate()
-        .filter(|(index, _)| index & 1 == 0)
-        .map(|(_, line)| line)
-        .map(|line| {
-            line.chars()
-                .enumerate()
-                .filter(|(_, c)| *c != '.')
-                .map(|(index, _)| index)
-                .collect::<BTreeSet<_>>()
-        })
-        .map(|x| (0, x))
-        .reduce(|(nr, acc), (_, e)| {
-            let (collisions, misses): (BTreeSet<_>, BTreeSet<_>) =
-                acc.into_iter().partition(|n| e.contains(n));
-            (
-                nr + collisions.len(),
-                collisions
-                    .into_iter()
-                    .flat_map(|n| [n - 1, n + 1])
-                    .chain(misses)
-                    .collect(),
-            )
-        })
-        .unwrap()
-        .0
-        .try_into()
-        .unwrap()
-}
+use std::collections::{BTreeMap, BTreeSet};
 
-pub fn solve_b(input: &str) -> u64 {
-    0
-}
+use anyhow::{Context, Result, bail};
+use itertools::Itertools;
 
-#[cfg(test)]
-mod tests {
-    const INPUT: &'static str = r#".......S.......
+use crate::{
+    Part,
+    answer::Answer,
+    grid::Grid,
+    solver::{Ctx, Day, Example},
+    visual::{Frame, Visualise},
+};
+
+const EXAMPLES: &[Example] = &[Example {
+    input: r#".......S.......
 ...............
 .......^.......
 ...............
@@ -54,15 +28,197 @@ mod tests {
 ...............
 .^.^.^.^.^...^.
 ...............
-"#;
+"#,
+    part_one: Some("21"),
+    part_two: Some("40"),
+}];
+
+pub struct Day7;
+
+pub struct Manifold {
+    grid: Grid<char>,
+    /// The positions of the start and the splitters on every other line, as the lines in between
+    /// are empty.
+    rows: Vec<BTreeSet<usize>>,
+}
+
+impl Day for Day7 {
+    type Input = Manifold;
+
+    fn day(&self) -> i32 {
+        7
+    }
+
+    fn title(&self) -> &'static str {
+        "Laboratories"
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        EXAMPLES
+    }
+
+    fn parse(&self, input: &str) -> Result<Self::Input> {
+        parse(input)
+    }
+
+    fn solve_a(&self, input: &Self::Input, ctx: &Ctx) -> Result<Answer> {
+        Ok(solve_a(input, ctx)?.into())
+    }
+
+    fn solve_b(&self, input: &Self::Input, _ctx: &Ctx) -> Result<Answer> {
+        Ok(solve_b(input)?.into())
+    }
+
+    fn visualisation(&self) -> Option<&dyn Visualise<Self::Input>> {
+        Some(self)
+    }
+}
+
+impl Visualise<Manifold> for Day7 {
+    /// One frame per row of splitters, tracing the beams that reached it and highlighting the
+    /// splitters they hit. Both parts follow the same beams.
+    fn frames(&self, _part: Part, manifold: &Manifold, ctx: &Ctx) -> Result<Vec<Frame>> {
+        let Manifold { grid, rows } = manifold;
+        let mut grid = grid.clone();
+        let width = grid.width();
+        let mut beams = rows.first().context("Empty input")?.clone();
+
+        let mut splits = 0;
+        let mut frames = vec![Frame {
+            grid: grid.clone(),
+            highlights: BTreeSet::new(),
+            caption: format!("{} beams", beams.len()),
+        }];
+        for (y, row) in rows.iter().enumerate().skip(1) {
+            ctx.check()?;
+            // Splitters are on even lines, so beams cross the odd line above to reach row `y`.
+            let line = 2 * y;
+            for &x in &beams {
+                grid[(line - 1, x)] = '|';
+            }
+            let (hit, passed): (BTreeSet<_>, BTreeSet<_>) =
+                beams.into_iter().partition(|x| row.contains(x));
+            splits += hit.len();
+            beams = passed;
+            for &x in &hit {
+                beams.extend(split(x, width)?);
+            }
+            for &x in &beams {
+                grid[(line, x)] = '|';
+            }
+            frames.push(Frame {
+                grid: grid.clone(),
+                highlights: hit.iter().map(|&x| (line, x)).collect(),
+                caption: format!("{} beams, {splits} splits", beams.len()),
+            });
+        }
+        Ok(frames)
+    }
+}
+
+/// Keeps every other row, as the rows in between are empty.
+fn parse(input: &str) -> Result<Manifold> {
+    let grid = Grid::parse(input)?;
+    let rows = grid
+        .rows()
+        .step_by(2)
+        .map(|row| row.iter().positions(|&c| c != '.').collect())
+        .collect();
+    Ok(Manifold { grid, rows })
+}
+
+/// The positions either side of a splitter at `x`, failing if one of them is off the grid rather
+/// than losing the beam that would go there.
+fn split(x: usize, width: usize) -> Result<[usize; 2]> {
+    match x.checked_sub(1) {
+        Some(left) if x + 1 < width => Ok([left, x + 1]),
+        _ => bail!("Beam left the manifold at column {x}"),
+    }
+}
+
+fn solve_a(manifold: &Manifold, ctx: &Ctx) -> Result<u64> {
+    let width = manifold.grid.width();
+    let (start, rows) = manifold.rows.split_first().context("Empty input")?;
+    let mut beams = start.clone();
+    let mut splits = 0;
+    for row in rows {
+        let (hit, passed): (BTreeSet<_>, BTreeSet<_>) =
+            beams.into_iter().partition(|x| row.contains(x));
+        ctx.log(format_args!(
+            "{} beams split, {} passed",
+            hit.len(),
+            passed.len()
+        ));
+        splits += hit.len();
+        ctx.value("splits", splits);
+        beams = passed;
+        for &x in &hit {
+            beams.extend(split(x, width)?);
+        }
+    }
+    Ok(splits.try_into()?)
+}
+
+/// Counts the timelines ending at each position, row by row, so the work depends on the size of
+/// the grid rather than on the number of timelines, which grows exponentially with the rows.
+fn solve_b(manifold: &Manifold) -> Result<u128> {
+    let width = manifold.grid.width();
+    let (start, rows) = manifold.rows.split_first().context("Empty input")?;
+    let mut timelines: BTreeMap<usize, u128> = start.iter().map(|&n| (n, 1)).collect();
+    for row in rows {
+        let mut next = BTreeMap::new();
+        for (n, count) in timelines {
+            let targets = if row.contains(&n) {
+                split(n, width)?.map(Some)
+            } else {
+                [Some(n), None]
+            };
+            for target in targets.into_iter().flatten() {
+                let total: &mut u128 = next.entry(target).or_default();
+                *total = total.checked_add(count).context("Too many timelines")?;
+            }
+        }
+        timelines = next;
+    }
+    timelines
+        .into_values()
+        .try_fold(0u128, u128::checked_add)
+        .context("Too many timelines")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Day7, EXAMPLES, parse};
+    use crate::{Part, solver::Ctx, visual::Visualise};
 
     #[test]
     fn test_a() {
-        assert_eq!(super::solve_a(INPUT), 21);
+        assert_eq!(
+            super::solve_a(&parse(EXAMPLES[0].input).unwrap(), &Ctx::default()).unwrap(),
+            21
+        );
     }
 
     #[test]
     fn test_b() {
-        assert_eq!(super::solve_b(INPUT), 40);
+        assert_eq!(
+            super::solve_b(&parse(EXAMPLES[0].input).unwrap()).unwrap(),
+            40
+        );
+    }
+
+    #[test]
+    fn test_splitter_at_edge() {
+        for input in ["S..\n...\n^..\n", "..S\n...\n..^\n"] {
+            let manifold = parse(input).unwrap();
+            let ctx = Ctx::default();
+            let error = "Beam left the manifold";
+            let a = super::solve_a(&manifold, &ctx).unwrap_err();
+            assert!(a.to_string().starts_with(error), "{input:?}: {a}");
+            let b = super::solve_b(&manifold).unwrap_err();
+            assert!(b.to_string().starts_with(error), "{input:?}: {b}");
+            let frames = Day7.frames(Part::One, &manifold, &ctx).err().unwrap();
+            assert!(frames.to_string().starts_with(error), "{input:?}: {frames}");
+        }
     }
 }
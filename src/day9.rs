@@ -1,7 +0,0 @@
-pub fn solve_a(input: &str) -> u64 {
-    0
-}
-
-pub fn solve_b(input: &str) -> u64 {
-    0
-}
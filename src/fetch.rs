@@ -0,0 +1,216 @@
+use std::{
+    env,
+    fs::{self, OpenOptions},
+    io::Write,
+    path::{Path, PathBuf},
+    process::ExitCode,
+};
+
+use anyhow::{Context, Result, bail};
+use serde::Deserialize;
+
+use crate::{
+    input::{self, Inputs},
+    solver,
+};
+
+/// Environment variable holding the session cookie, which takes precedence over the config file.
+const SESSION_VAR: &str = "AOC_SESSION";
+const BASE_URL: &str = "https://adventofcode.com";
+
+#[derive(Debug, clap::Args)]
+pub struct Options {
+    /// Day to download the input of.
+    #[arg(long)]
+    day: i32,
+    /// Server to download from, instead of the one in the config file or Advent of Code itself.
+    #[arg(long)]
+    base_url: Option<String>,
+    /// File with the `session` cookie and optionally a `base_url`.
+    #[arg(long, default_value = "aoc.toml")]
+    config: PathBuf,
+}
+
+/// Settings for talking to the Advent of Code website, as kept in `aoc.toml`.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Config {
+    session: Option<String>,
+    base_url: Option<String>,
+}
+
+impl Config {
+    /// Reads the config, which may not exist if the session is in the environment.
+    pub fn load(path: &Path) -> Result<Self> {
+        input::load_or_default(path, |config| {
+            toml::from_str(config).with_context(|| format!("Failed to parse {}", path.display()))
+        })
+    }
+}
+
+/// Downloads a day's input to where it will be read from.
+pub fn run(year: i32, inputs: &Inputs, options: &Options) -> ExitCode {
+    match fetch_day(year, inputs, options) {
+        Ok(path) => {
+            println!("Saved day {} to {}", options.day, path.display());
+            ExitCode::SUCCESS
+        }
+        Err(error) => {
+            eprintln!("{error:#}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn fetch_day(year: i32, inputs: &Inputs, options: &Options) -> Result<PathBuf> {
+    let day = options.day;
+    check_day(year, day)?;
+    let path = inputs.path(year, day);
+    if path.exists() {
+        bail!("{} already exists", path.display());
+    }
+    let config = Config::load(&options.config)?;
+    let session = match env::var(SESSION_VAR) {
+        Ok(session) => session,
+        Err(_) => config.session.with_context(|| {
+            format!(
+                "No session cookie in {SESSION_VAR} or {}",
+                options.config.display()
+            )
+        })?,
+    };
+    let base_url = options
+        .base_url
+        .as_deref()
+        .or(config.base_url.as_deref())
+        .unwrap_or(BASE_URL);
+    fetch(base_url, session.trim(), year, day, &path)?;
+    Ok(path)
+}
+
+/// Fails unless the event of `year` has a day `day`, whether or not it has been solved.
+fn check_day(year: i32, day: i32) -> Result<()> {
+    match solver::event_days(year) {
+        None => bail!("There was no Advent of Code in {year}"),
+        Some(days) if !(1..=days).contains(&day) => bail!("{year} has no day {day}"),
+        Some(_) => Ok(()),
+    }
+}
+
+/// Downloads an input to `path`, unless that already exists, so inputs are only requested once.
+fn fetch(base_url: &str, session: &str, year: i32, day: i32, path: &Path) -> Result<()> {
+    if path.exists() {
+        bail!("{} already exists", path.display());
+    }
+    let url = format!("{}/{year}/day/{day}/input", base_url.trim_end_matches('/'));
+    let input = ureq::get(&url)
+        .header("Cookie", format!("session={session}"))
+        .header(
+            "User-Agent",
+            concat!(env!("CARGO_PKG_NAME"), "/", env!("CARGO_PKG_VERSION")),
+        )
+        .call()
+        .and_then(|mut response| response.body_mut().read_to_string())
+        .with_context(|| format!("Failed to download {url}"))?;
+
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+    }
+    // Don't overwrite an input that appeared while downloading.
+    let mut file = OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(path)
+        .with_context(|| format!("Failed to create {}", path.display()))?;
+    file.write_all(input.as_bytes())
+        .with_context(|| format!("Failed to write {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        fs,
+        io::{BufRead, BufReader, Write},
+        net::TcpListener,
+        path::PathBuf,
+        thread::{self, JoinHandle},
+    };
+
+    use super::{check_day, fetch};
+
+    /// Serves one request with `status` and `body`, returning the request line and headers.
+    fn serve(status: &str, body: &'static str) -> (String, JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let status = status.to_string();
+        let handle = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let request = BufReader::new(&stream)
+                .lines()
+                .map(Result::unwrap)
+                .take_while(|line| !line.is_empty())
+                .collect();
+            write!(
+                stream,
+                "HTTP/1.1 {status}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            )
+            .unwrap();
+            request
+        });
+        (url, handle)
+    }
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("aoc-fetch-{}-{name}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn test_fetch() {
+        let dir = temp_dir("ok");
+        let path = dir.join("2025/day3.txt");
+        let (url, server) = serve("200 OK", "987654321111111\n");
+
+        fetch(&format!("{url}/"), "secret", 2025, 3, &path).unwrap();
+        let request = server.join().unwrap();
+        assert_eq!(request[0], "GET /2025/day/3/input HTTP/1.1");
+        assert!(
+            request
+                .iter()
+                .any(|line| line.eq_ignore_ascii_case("cookie: session=secret"))
+        );
+        assert_eq!(fs::read_to_string(&path).unwrap(), "987654321111111\n");
+
+        // The server is gone, so this only passes if nothing is requested.
+        let error = fetch(&url, "secret", 2025, 3, &path).unwrap_err();
+        assert!(error.to_string().contains("already exists"));
+        assert_eq!(fs::read_to_string(&path).unwrap(), "987654321111111\n");
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_check_day() {
+        assert!(check_day(2024, 25).is_ok());
+        assert!(check_day(2025, 12).is_ok());
+        let error = |year, day| check_day(year, day).unwrap_err().to_string();
+        assert_eq!(error(2025, 13), "2025 has no day 13");
+        assert_eq!(error(2024, 0), "2024 has no day 0");
+        assert_eq!(error(2014, 1), "There was no Advent of Code in 2014");
+    }
+
+    #[test]
+    fn test_fetch_failure() {
+        let dir = temp_dir("failure");
+        let path = dir.join("2025/day3.txt");
+        let (url, server) = serve(
+            "404 Not Found",
+            "Please don't repeatedly request this endpoint",
+        );
+
+        assert!(fetch(&url, "secret", 2025, 3, &path).is_err());
+        server.join().unwrap();
+        assert!(!path.exists());
+    }
+}
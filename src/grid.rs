@@ -0,0 +1,302 @@
+use std::{
+    fmt::{Display, Formatter},
+    ops::{Index, IndexMut},
+};
+
+use anyhow::{Result, bail};
+
+/// A position in a grid, as `(row, col)`.
+pub type Pos = (usize, usize);
+
+/// A rectangular grid, stored row by row.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+#[allow(dead_code)]
+const NEIGHBOURS_4: [(isize, isize); 4] = [(-1, 0), (0, -1), (0, 1), (1, 0)];
+const NEIGHBOURS_8: [(isize, isize); 8] = [
+    (-1, -1),
+    (-1, 0),
+    (-1, 1),
+    (0, -1),
+    (0, 1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+];
+
+impl Grid<char> {
+    /// Parses one row per line. All lines must be equally long.
+    pub fn parse(input: &str) -> Result<Self> {
+        Self::parse_with(input, Ok)
+    }
+}
+
+impl<T> Grid<T> {
+    /// Parses one row per line, turning each character into a cell with `f`.
+    pub fn parse_with(input: &str, mut f: impl FnMut(char) -> Result<T>) -> Result<Self> {
+        let rows = input
+            .lines()
+            .map(|line| line.chars().map(&mut f).collect())
+            .collect::<Result<_>>()?;
+        Self::from_rows(rows)
+    }
+
+    /// Fails if the rows are not all equally long.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Result<Self> {
+        let width = rows.first().map_or(0, Vec::len);
+        if let Some(index) = rows.iter().position(|row| row.len() != width) {
+            bail!(
+                "Row {} is {} wide where row 1 is {width}",
+                index + 1,
+                rows[index].len()
+            );
+        }
+        Ok(Self {
+            width,
+            height: rows.len(),
+            cells: rows.into_iter().flatten().collect(),
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, (row, col): Pos) -> bool {
+        row < self.height && col < self.width
+    }
+
+    pub fn get(&self, pos: Pos) -> Option<&T> {
+        self.contains(pos).then(|| &self.cells[self.offset(pos)])
+    }
+
+    #[allow(dead_code)]
+    pub fn get_mut(&mut self, pos: Pos) -> Option<&mut T> {
+        let offset = self.contains(pos).then(|| self.offset(pos))?;
+        Some(&mut self.cells[offset])
+    }
+
+    /// All positions, row by row.
+    pub fn positions(&self) -> impl Iterator<Item = Pos> + use<T> {
+        let width = self.width;
+        (0..self.height).flat_map(move |row| (0..width).map(move |col| (row, col)))
+    }
+
+    /// All cells with their positions, row by row.
+    #[allow(dead_code)]
+    pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> {
+        self.positions().zip(&self.cells)
+    }
+
+    /// The positions above, left, right and below `pos` that are in the grid.
+    #[allow(dead_code)]
+    pub fn neighbours4(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        self.offsets(pos, &NEIGHBOURS_4)
+    }
+
+    /// The positions around `pos`, including diagonally, that are in the grid.
+    pub fn neighbours8(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        self.offsets(pos, &NEIGHBOURS_8)
+    }
+
+    pub fn row(&self, row: usize) -> &[T] {
+        &self.cells[row * self.width..(row + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // `chunks` would panic on a zero width.
+        (0..self.height).map(|row| self.row(row))
+    }
+
+    pub fn col(&self, col: usize) -> impl Iterator<Item = &T> {
+        assert!(col < self.width, "column {col} is out of bounds");
+        self.cells.iter().skip(col).step_by(self.width)
+    }
+
+    pub fn cols(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|col| self.col(col))
+    }
+
+    /// The diagonals running down and to the right, starting with the one in the bottom left
+    /// corner.
+    #[allow(dead_code)]
+    pub fn diagonals(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width + self.height).skip(1).map(|k| {
+            // Cells on diagonal `k` have `col - row == k - height`.
+            let (row, col) = (self.height.saturating_sub(k), k.saturating_sub(self.height));
+            (0..)
+                .map(move |i| (row + i, col + i))
+                .map_while(|pos| self.get(pos))
+        })
+    }
+
+    /// The diagonals running down and to the left, starting with the one in the top left corner.
+    #[allow(dead_code)]
+    pub fn anti_diagonals(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..(self.width + self.height).saturating_sub(1)).map(|k| {
+            // Cells on diagonal `k` have `row + col == k`.
+            let last = self.width.saturating_sub(1);
+            let (row, col) = (k.saturating_sub(last), k.min(last));
+            (0..)
+                .map_while(move |i| Some((row + i, col.checked_sub(i)?)))
+                .map_while(|pos| self.get(pos))
+        })
+    }
+
+    /// The first position holding `value`, row by row.
+    #[allow(dead_code)]
+    pub fn find(&self, value: &T) -> Option<Pos>
+    where
+        T: PartialEq,
+    {
+        self.find_all(value).next()
+    }
+
+    #[allow(dead_code)]
+    pub fn find_all<'a>(&'a self, value: &'a T) -> impl Iterator<Item = Pos> + 'a
+    where
+        T: PartialEq,
+    {
+        self.iter()
+            .filter(move |(_, cell)| *cell == value)
+            .map(|(pos, _)| pos)
+    }
+
+    /// Swaps rows and columns.
+    pub fn transpose(&self) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            width: self.height,
+            height: self.width,
+            cells: self.cols().flatten().cloned().collect(),
+        }
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+
+    fn offset(&self, (row, col): Pos) -> usize {
+        row * self.width + col
+    }
+
+    fn offsets<'a>(
+        &'a self,
+        (row, col): Pos,
+        offsets: &'static [(isize, isize)],
+    ) -> impl Iterator<Item = Pos> + 'a {
+        offsets
+            .iter()
+            .filter_map(move |&(dr, dc)| {
+                Some((row.checked_add_signed(dr)?, col.checked_add_signed(dc)?))
+            })
+            .filter(|&pos| self.contains(pos))
+    }
+}
+
+impl<T> Index<Pos> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Pos) -> &T {
+        self.get(pos)
+            .unwrap_or_else(|| panic!("{pos:?} is out of bounds"))
+    }
+}
+
+impl<T> IndexMut<Pos> for Grid<T> {
+    fn index_mut(&mut self, pos: Pos) -> &mut T {
+        assert!(self.contains(pos), "{pos:?} is out of bounds");
+        let offset = self.offset(pos);
+        &mut self.cells[offset]
+    }
+}
+
+/// One line per row, without separators between cells.
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for (index, row) in self.rows().enumerate() {
+            if index > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Grid;
+
+    const INPUT: &str = "abc\ndef\n";
+
+    fn collect<'a>(lines: impl Iterator<Item = impl Iterator<Item = &'a char>>) -> Vec<String> {
+        lines.map(|line| line.collect()).collect()
+    }
+
+    #[test]
+    fn test_parse() {
+        let mut grid = Grid::parse(INPUT).unwrap();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[(1, 2)], 'f');
+        assert_eq!(grid.get((2, 0)), None);
+        assert_eq!(grid.get((0, 3)), None);
+        assert_eq!(grid.get((usize::MAX, 0)), None);
+        assert_eq!(grid.get_mut((usize::MAX, 0)), None);
+        assert_eq!(grid.get_mut((0, 3)), None);
+        *grid.get_mut((0, 1)).unwrap() = 'B';
+        assert_eq!(grid.to_string(), INPUT.trim_end().replace('b', "B"));
+        assert!(Grid::parse("ab\nc\n").is_err());
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let grid = Grid::parse(INPUT).unwrap();
+        let mut neighbours: Vec<_> = grid.neighbours4((0, 0)).collect();
+        neighbours.sort();
+        assert_eq!(neighbours, [(0, 1), (1, 0)]);
+        assert_eq!(grid.neighbours8((0, 1)).count(), 5);
+        assert_eq!(grid.neighbours8((1, 1)).count(), 5);
+    }
+
+    #[test]
+    fn test_lines() {
+        let grid = Grid::parse(INPUT).unwrap();
+        assert_eq!(collect(grid.rows().map(<[_]>::iter)), ["abc", "def"]);
+        assert_eq!(collect(grid.cols()), ["ad", "be", "cf"]);
+        assert_eq!(collect(grid.diagonals()), ["d", "ae", "bf", "c"]);
+        assert_eq!(collect(grid.anti_diagonals()), ["a", "bd", "ce", "f"]);
+    }
+
+    #[test]
+    fn test_transpose() {
+        let grid = Grid::parse(INPUT).unwrap();
+        assert_eq!(grid.transpose().to_string(), "ad\nbe\ncf");
+        assert_eq!(grid.transpose().transpose(), grid);
+    }
+
+    #[test]
+    fn test_find() {
+        let grid = Grid::parse(".#.\n#..\n").unwrap();
+        assert_eq!(grid.find(&'#'), Some((0, 1)));
+        assert_eq!(grid.find_all(&'#').count(), 2);
+        assert_eq!(grid.find(&'x'), None);
+    }
+}
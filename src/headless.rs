@@ -0,0 +1,127 @@
+use std::{path::Path, process::ExitCode};
+
+use crate::{
+    Part,
+    answer::Answer,
+    answers::Answers,
+    input::Inputs,
+    panic,
+    solver::{self, Ctx, Output, Puzzle, Registry},
+};
+
+pub fn run(registry: &Registry, inputs: &Inputs, day: Option<i32>, part: Option<Part>) -> ExitCode {
+    let mut failed = false;
+    let complete = solve(registry, inputs, day, part, |day, part, output| {
+        print(day, part, &output, "");
+        failed |= !output.matches(None);
+    });
+    exit_code(complete && !failed)
+}
+
+/// Compares answers against the recorded ones, or records them instead if `update` is set.
+pub fn verify(
+    registry: &Registry,
+    inputs: &Inputs,
+    path: &Path,
+    day: Option<i32>,
+    part: Option<Part>,
+    update: bool,
+) -> ExitCode {
+    let mut answers = match Answers::load(path) {
+        Ok(answers) => answers,
+        Err(error) => {
+            eprintln!("{error:#}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let mut failed = false;
+    let complete = solve(registry, inputs, day, part, |day, part, output| {
+        if update {
+            print(day, part, &output, "");
+            failed |= !output.matches(None);
+            if let Output::Solved(answer, _) = output {
+                answers.set(registry.year(), day, part, answer.to_string());
+            }
+        } else {
+            let expected = answers.get(registry.year(), day, part);
+            let mark = match expected {
+                None => " (not recorded)".to_string(),
+                Some(_) if output.matches(expected) => " ✓".to_string(),
+                Some(expected) => format!(" ✗ expected {expected}"),
+            };
+            print(day, part, &output, &mark);
+            failed |= !output.matches(expected);
+        }
+    });
+
+    if update && let Err(error) = answers.save(path) {
+        eprintln!("{error:#}");
+        return ExitCode::FAILURE;
+    }
+    exit_code(complete && !failed)
+}
+
+/// Runs the selected solvers one after another, handing each output to `f`. Returns whether all of
+/// them could be started.
+fn solve(
+    registry: &Registry,
+    inputs: &Inputs,
+    day: Option<i32>,
+    part: Option<Part>,
+    mut f: impl FnMut(i32, Part, Output),
+) -> bool {
+    let solvers = match registry.select(day) {
+        Ok(solvers) => solvers,
+        Err(error) => {
+            eprintln!("{error}");
+            return false;
+        }
+    };
+
+    panic::install_hook();
+    let mut complete = true;
+    for solver in solvers {
+        let day = solver.day();
+        let input = match inputs.load(registry.year(), day) {
+            Ok(input) => input,
+            Err(error) => {
+                eprintln!("Day {day}: {error:#}");
+                complete = false;
+                continue;
+            }
+        };
+        let puzzle = Puzzle::new(solver, input);
+        for p in [Part::One, Part::Two] {
+            if part.is_none_or(|part| part == p) {
+                f(day, p, solver::run(&puzzle, p, &Ctx::default()));
+            }
+        }
+    }
+    complete
+}
+
+fn print(day: i32, part: Part, output: &Output, mark: &str) {
+    match output {
+        Output::Solved(answer, duration) => {
+            println!(
+                "Day {day} part {part}: {} ({:.5} s){mark}",
+                answer.summary(),
+                duration.as_secs_f64()
+            );
+            if let Answer::Grid(_) = answer {
+                println!("{answer}");
+            }
+        }
+        Output::Failed(error, _) => eprintln!("Day {day} part {part}: {error:#}"),
+        Output::Panicked(message) => eprintln!("Day {day} part {part}: panicked: {message}"),
+    }
+}
+
+fn exit_code(success: bool) -> ExitCode {
+    if success {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
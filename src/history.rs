@@ -0,0 +1,240 @@
+use std::{
+    fs::OpenOptions,
+    io::Write,
+    path::Path,
+    process::Command,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::{Part, input};
+
+/// Timings of earlier runs, kept as one JSON object per line so runs can be appended.
+#[derive(Debug, Default)]
+pub struct History {
+    runs: Vec<Run>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct Run {
+    /// Output of `git describe --always --dirty`, if available.
+    pub commit: Option<String>,
+    /// Seconds since the Unix epoch.
+    pub timestamp: u64,
+    /// What took the timings, which runs from before this was recorded don't say.
+    #[serde(default)]
+    pub origin: Option<Origin>,
+    #[serde(default)]
+    pub profile: Option<Profile>,
+    pub timings: Vec<Timing>,
+}
+
+/// What took the timings of a run, since a single dashboard run and the median of a benchmark
+/// aren't comparable.
+#[derive(Copy, Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Origin {
+    Bench,
+    Dashboard,
+}
+
+/// How the binary that took the timings was built.
+#[derive(Copy, Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Profile {
+    Debug,
+    Release,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct Timing {
+    pub year: i32,
+    pub day: i32,
+    pub part: Part,
+    #[serde(with = "secs")]
+    pub duration: Duration,
+}
+
+/// How a timing relates to the ones in the history.
+#[derive(Debug)]
+pub struct Comparison {
+    pub previous: Option<Duration>,
+    pub best: Option<Duration>,
+    /// Whether it is slower than the previous one by more than the threshold.
+    pub regressed: bool,
+}
+
+impl History {
+    /// Reads the runs taken by `origin` with a binary built like this one, of which a missing file
+    /// has none.
+    pub fn load(path: &Path, origin: Origin) -> Result<Self> {
+        let runs: Vec<Run> = input::load_or_default(path, |history| {
+            history
+                .lines()
+                .enumerate()
+                .filter(|(_, line)| !line.trim().is_empty())
+                .map(|(index, line)| {
+                    serde_json::from_str(line).with_context(|| {
+                        format!("Failed to parse {}:{}", path.display(), index + 1)
+                    })
+                })
+                .collect()
+        })?;
+        let runs = runs
+            .into_iter()
+            .filter(|run| run.origin == Some(origin) && run.profile == Some(Profile::current()))
+            .collect();
+        Ok(Self { runs })
+    }
+
+    /// Adds a run to the end of the file, without reading what is already there.
+    pub fn append(path: &Path, run: &Run) -> Result<()> {
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .with_context(|| format!("Failed to open {}", path.display()))?;
+        writeln!(file, "{}", serde_json::to_string(run)?)
+            .with_context(|| format!("Failed to write {}", path.display()))
+    }
+
+    /// The latest recorded timing of a part.
+    pub fn previous(&self, year: i32, day: i32, part: Part) -> Option<Duration> {
+        self.timings(year, day, part).last()
+    }
+
+    /// The fastest recorded timing of a part.
+    pub fn best(&self, year: i32, day: i32, part: Part) -> Option<Duration> {
+        self.timings(year, day, part).min()
+    }
+
+    /// Compares a timing against the history, where `threshold` is the fraction by which it may
+    /// be slower than the previous one.
+    pub fn compare(
+        &self,
+        year: i32,
+        day: i32,
+        part: Part,
+        duration: Duration,
+        threshold: f64,
+    ) -> Comparison {
+        let previous = self.previous(year, day, part);
+        Comparison {
+            previous,
+            best: self.best(year, day, part),
+            regressed: previous.is_some_and(|previous| {
+                duration.as_secs_f64() > previous.as_secs_f64() * (1.0 + threshold)
+            }),
+        }
+    }
+
+    fn timings(&self, year: i32, day: i32, part: Part) -> impl Iterator<Item = Duration> {
+        self.runs.iter().flat_map(move |run| {
+            run.timings
+                .iter()
+                .filter(move |timing| (timing.year, timing.day, timing.part) == (year, day, part))
+                .map(|timing| timing.duration)
+        })
+    }
+}
+
+impl Comparison {
+    /// Relative change compared to the previous timing, e.g. `0.5` for 50% slower.
+    pub fn change(&self, duration: Duration) -> Option<f64> {
+        let previous = self.previous?.as_secs_f64();
+        Some(duration.as_secs_f64() / previous - 1.0)
+    }
+}
+
+impl Run {
+    /// Records timings as taken by `origin` at the current commit and time.
+    pub fn new(origin: Origin, timings: Vec<Timing>) -> Self {
+        let commit = Command::new("git")
+            .args(["describe", "--always", "--dirty"])
+            .output()
+            .ok()
+            .filter(|output| output.status.success())
+            .and_then(|output| String::from_utf8(output.stdout).ok())
+            .map(|commit| commit.trim().to_string());
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |duration| duration.as_secs());
+        Self {
+            commit,
+            timestamp,
+            origin: Some(origin),
+            profile: Some(Profile::current()),
+            timings,
+        }
+    }
+}
+
+impl Profile {
+    /// The profile of the running binary.
+    pub fn current() -> Self {
+        if cfg!(debug_assertions) {
+            Self::Debug
+        } else {
+            Self::Release
+        }
+    }
+}
+
+/// (De)serializes a [`Duration`] as fractional seconds.
+pub mod secs {
+    use std::time::Duration;
+
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(duration: &Duration, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_f64(duration.as_secs_f64())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Duration, D::Error> {
+        let secs = f64::deserialize(deserializer)?;
+        Duration::try_from_secs_f64(secs).map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, time::Duration};
+
+    use super::{History, Origin, Run, Timing};
+    use crate::Part;
+
+    #[test]
+    fn test_load_compares_like_with_like() {
+        let path = std::env::temp_dir().join(format!("aoc-history-{}.jsonl", std::process::id()));
+        let _ = fs::remove_file(&path);
+        let run = |origin, secs| {
+            let timing = Timing {
+                year: 2025,
+                day: 1,
+                part: Part::One,
+                duration: Duration::from_secs(secs),
+            };
+            Run::new(origin, vec![timing])
+        };
+        History::append(&path, &run(Origin::Bench, 1)).unwrap();
+        History::append(&path, &run(Origin::Dashboard, 2)).unwrap();
+        let mut untagged = serde_json::to_value(run(Origin::Bench, 3)).unwrap();
+        untagged["origin"] = serde_json::Value::Null;
+        fs::write(
+            &path,
+            format!("{}{untagged}\n", fs::read_to_string(&path).unwrap()),
+        )
+        .unwrap();
+
+        let previous = |origin| {
+            History::load(&path, origin)
+                .unwrap()
+                .previous(2025, 1, Part::One)
+        };
+        assert_eq!(previous(Origin::Bench), Some(Duration::from_secs(1)));
+        assert_eq!(previous(Origin::Dashboard), Some(Duration::from_secs(2)));
+        fs::remove_file(path).unwrap();
+    }
+}
@@ -0,0 +1,71 @@
+use std::{
+    collections::BTreeMap,
+    fs,
+    io::ErrorKind,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{Context, Result, anyhow};
+
+/// Where to find puzzle inputs: `YEAR/dayN.txt` in a directory, unless overridden.
+pub struct Inputs {
+    dir: PathBuf,
+    /// The year the overrides are for.
+    year: i32,
+    overrides: BTreeMap<i32, PathBuf>,
+}
+
+impl Inputs {
+    pub fn new(
+        dir: impl Into<PathBuf>,
+        year: i32,
+        overrides: impl IntoIterator<Item = (i32, PathBuf)>,
+    ) -> Self {
+        Self {
+            dir: dir.into(),
+            year,
+            overrides: overrides.into_iter().collect(),
+        }
+    }
+
+    pub fn path(&self, year: i32, day: i32) -> PathBuf {
+        self.overrides
+            .get(&day)
+            .filter(|_| year == self.year)
+            .cloned()
+            .unwrap_or_else(|| {
+                self.dir
+                    .join(year.to_string())
+                    .join(format!("day{day}.txt"))
+            })
+    }
+
+    pub fn load(&self, year: i32, day: i32) -> Result<String> {
+        let path = self.path(year, day);
+        fs::read_to_string(&path).with_context(|| format!("Failed to read {}", path.display()))
+    }
+}
+
+/// Reads a file and parses it with `parse`, treating a missing file as the default, as for files
+/// that are only written once there is something to record.
+pub fn load_or_default<T: Default>(
+    path: &Path,
+    parse: impl FnOnce(&str) -> Result<T>,
+) -> Result<T> {
+    match fs::read_to_string(path) {
+        Ok(contents) => parse(&contents),
+        Err(error) if error.kind() == ErrorKind::NotFound => Ok(T::default()),
+        Err(error) => Err(error).with_context(|| format!("Failed to read {}", path.display())),
+    }
+}
+
+/// Parses a `DAY=PATH` override as given on the command line.
+pub fn parse_override(s: &str) -> Result<(i32, PathBuf)> {
+    let (day, path) = s
+        .split_once('=')
+        .ok_or(anyhow!("Expected DAY=PATH, got {s}"))?;
+    let day = day
+        .parse()
+        .with_context(|| format!("Failed to parse day of {s}"))?;
+    Ok((day, path.into()))
+}
@@ -0,0 +1,317 @@
+use std::{
+    cmp::Ordering,
+    fmt::{Debug, Display, Formatter},
+    num::ParseIntError,
+    ops::{Add, Sub},
+    str::FromStr,
+};
+
+use anyhow::{Context, Error, Result, anyhow, bail};
+
+/// The unsigned integers ranges can be made of.
+pub trait Int:
+    Copy
+    + Ord
+    + Debug
+    + Display
+    + FromStr<Err = ParseIntError>
+    + Add<Output = Self>
+    + Sub<Output = Self>
+{
+    const ONE: Self;
+
+    fn saturating_add(self, other: Self) -> Self;
+
+    /// The same integer as a `u128`, so lengths can be counted in one type.
+    fn widen(self) -> u128;
+}
+
+macro_rules! impl_int {
+    ($($int:ty),*) => {
+        $(impl Int for $int {
+            const ONE: Self = 1;
+
+            fn saturating_add(self, other: Self) -> Self {
+                <$int>::saturating_add(self, other)
+            }
+
+            fn widen(self) -> u128 {
+                u128::from(self)
+            }
+        })*
+    };
+}
+
+impl_int!(u64, u128);
+
+/// An inclusive range of integers, written as `a-b`.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct Range<T = u64> {
+    pub start: T,
+    pub end: T,
+}
+
+/// Disjoint ranges in order, with no two of them adjacent, so every set has one representation.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct RangeSet<T = u64> {
+    ranges: Vec<Range<T>>,
+}
+
+impl<T: Int> Range<T> {
+    /// Fails if `start` is after `end`.
+    pub fn new(start: T, end: T) -> Result<Self> {
+        if start > end {
+            bail!("Range {start}-{end} ends before it starts");
+        }
+        Ok(Self { start, end })
+    }
+
+    /// Number of integers in the range, if it fits in a `u128`, which only the full range of
+    /// `u128` doesn't.
+    pub fn len(&self) -> Option<u128> {
+        (self.end - self.start).widen().checked_add(1)
+    }
+
+    #[allow(dead_code)]
+    pub fn contains(&self, n: T) -> bool {
+        self.start <= n && n <= self.end
+    }
+
+    #[allow(dead_code)]
+    pub fn overlaps(&self, other: &Self) -> bool {
+        self.start <= other.end && other.start <= self.end
+    }
+}
+
+impl<T: Int> RangeSet<T> {
+    pub fn ranges(&self) -> &[Range<T>] {
+        &self.ranges
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Range<T>> {
+        self.ranges.iter()
+    }
+
+    #[allow(dead_code)]
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// Number of integers in the set, if it fits in a `u128`.
+    pub fn len(&self) -> Option<u128> {
+        self.ranges
+            .iter()
+            .try_fold(0u128, |len, range| len.checked_add(range.len()?))
+    }
+
+    /// Whether any range contains `n`, in O(log n).
+    pub fn contains(&self, n: T) -> bool {
+        self.ranges
+            .binary_search_by(|range| {
+                if range.end < n {
+                    Ordering::Less
+                } else if range.start > n {
+                    Ordering::Greater
+                } else {
+                    Ordering::Equal
+                }
+            })
+            .is_ok()
+    }
+
+    #[allow(dead_code)]
+    pub fn union(&self, other: &Self) -> Self {
+        self.iter().chain(other.iter()).copied().collect()
+    }
+
+    #[allow(dead_code)]
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut ranges = Vec::new();
+        let (mut a, mut b) = (self.iter().peekable(), other.iter().peekable());
+        while let (Some(x), Some(y)) = (a.peek(), b.peek()) {
+            let (start, end) = (x.start.max(y.start), x.end.min(y.end));
+            if start <= end {
+                ranges.push(Range { start, end });
+            }
+            // Whichever ends first can't overlap anything further along the other set.
+            if x.end < y.end {
+                a.next();
+            } else {
+                b.next();
+            }
+        }
+        Self { ranges }
+    }
+
+    #[allow(dead_code)]
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut ranges = Vec::new();
+        let mut others = other.iter().peekable();
+        for &range in self.iter() {
+            let mut start = range.start;
+            while others.next_if(|other| other.end < start).is_some() {}
+            // Cut out the ranges of `other` that overlap this one, leaving the part after them.
+            let mut remaining = true;
+            while let Some(other) = others.peek()
+                && other.start <= range.end
+            {
+                if other.start > start {
+                    ranges.push(Range {
+                        start,
+                        end: other.start - T::ONE,
+                    });
+                }
+                if other.end >= range.end {
+                    remaining = false;
+                    break;
+                }
+                start = other.end + T::ONE;
+                others.next();
+            }
+            if remaining {
+                ranges.push(Range {
+                    start,
+                    end: range.end,
+                });
+            }
+        }
+        Self { ranges }
+    }
+}
+
+/// Sorts and merges the ranges.
+impl<T: Int> FromIterator<Range<T>> for RangeSet<T> {
+    fn from_iter<I: IntoIterator<Item = Range<T>>>(iter: I) -> Self {
+        let mut sorted: Vec<_> = iter.into_iter().collect();
+        sorted.sort_by_key(|range| range.start);
+        let mut ranges: Vec<Range<T>> = Vec::with_capacity(sorted.len());
+        for range in sorted {
+            match ranges.last_mut() {
+                Some(last) if range.start <= last.end.saturating_add(T::ONE) => {
+                    last.end = last.end.max(range.end);
+                }
+                _ => ranges.push(range),
+            }
+        }
+        Self { ranges }
+    }
+}
+
+impl<T: Int> FromStr for Range<T> {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (start, end) = s.split_once('-').ok_or(anyhow!("No hyphen in {s}"))?;
+        let start = start
+            .parse()
+            .with_context(|| format!("Failed to parse start of range {s}"))?;
+        let end = end
+            .parse()
+            .with_context(|| format!("Failed to parse end of range {s}"))?;
+        Self::new(start, end)
+    }
+}
+
+/// Parses ranges separated by commas or whitespace, such as one per line.
+impl<T: Int> FromStr for RangeSet<T> {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.split(|c: char| c == ',' || c.is_whitespace())
+            .filter(|range| !range.is_empty())
+            .map(Range::from_str)
+            .collect()
+    }
+}
+
+impl<T: Int> Display for Range<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}-{}", self.start, self.end)
+    }
+}
+
+impl<T: Int> Display for RangeSet<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for (index, range) in self.ranges.iter().enumerate() {
+            if index > 0 {
+                write!(f, ",")?;
+            }
+            write!(f, "{range}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RangeSet;
+
+    fn set(s: &str) -> RangeSet {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(set("3-5\n10-14\n16-20\n12-18\n").to_string(), "3-5,10-20");
+        assert_eq!(set("1-2,3-4, 6-6").to_string(), "1-4,6-6");
+        assert_eq!(set("").to_string(), "");
+        assert!("5-3".parse::<RangeSet>().is_err());
+        assert!("5".parse::<RangeSet>().is_err());
+        assert!("a-3".parse::<RangeSet>().is_err());
+    }
+
+    #[test]
+    fn test_contains() {
+        let ranges = set("3-5,10-14,16-20");
+        let contained: Vec<_> = (0..22).filter(|&n| ranges.contains(n)).collect();
+        assert_eq!(contained, [3, 4, 5, 10, 11, 12, 13, 14, 16, 17, 18, 19, 20]);
+        assert_eq!(ranges.len(), Some(13));
+        assert_eq!(set("0-18446744073709551615").len(), Some(1 << 64));
+        let wide = |s: &str| s.parse::<RangeSet<u128>>().unwrap().len();
+        assert_eq!(
+            wide("5-9,100000000000000000000-100000000000000000001"),
+            Some(7)
+        );
+        assert_eq!(
+            wide("1-340282366920938463463374607431768211455"),
+            Some(u128::MAX)
+        );
+        assert_eq!(wide("0-340282366920938463463374607431768211455"), None);
+        assert_eq!(
+            wide("0-0,2-340282366920938463463374607431768211455"),
+            Some(u128::MAX)
+        );
+    }
+
+    #[test]
+    fn test_operations() {
+        let a = set("1-10,20-30,40-40");
+        let b = set("5-22,25-25,28-45");
+        assert_eq!(a.union(&b).to_string(), "1-45");
+        assert_eq!(
+            a.intersection(&b).to_string(),
+            "5-10,20-22,25-25,28-30,40-40"
+        );
+        assert_eq!(a.difference(&b).to_string(), "1-4,23-24,26-27");
+        assert_eq!(b.difference(&a).to_string(), "11-19,31-39,41-45");
+        assert_eq!(a.difference(&RangeSet::default()), a);
+        assert!(a.difference(&a).is_empty());
+    }
+
+    #[test]
+    fn test_operations_match_brute_force() {
+        let sets = ["", "0-0", "1-3,7-9", "0-5,8-8", "2-2,4-6,9-12", "0-20"].map(set);
+        for a in &sets {
+            for b in &sets {
+                let check = |result: &RangeSet, f: fn(bool, bool) -> bool| {
+                    for n in 0..25 {
+                        assert_eq!(result.contains(n), f(a.contains(n), b.contains(n)));
+                    }
+                };
+                check(&a.union(b), |x, y| x || y);
+                check(&a.intersection(b), |x, y| x && y);
+                check(&a.difference(b), |x, y| x && !y);
+            }
+        }
+    }
+}
@@ -0,0 +1,109 @@
+use std::{
+    collections::BTreeMap,
+    thread::{self, JoinHandle},
+};
+
+use crate::solver::Ctx;
+
+/// A computation running on its own thread, which can be asked to stop.
+pub struct Job<T> {
+    handle: Option<JoinHandle<T>>,
+    ctx: Ctx,
+    output: Option<T>,
+}
+
+impl<T: Send + 'static> Job<T> {
+    pub fn spawn(f: impl FnOnce(Ctx) -> T + Send + 'static) -> Self {
+        let ctx = Ctx::default();
+        let handle = {
+            let ctx = ctx.clone();
+            thread::spawn(move || f(ctx))
+        };
+        Self {
+            handle: Some(handle),
+            ctx,
+            output: None,
+        }
+    }
+}
+
+impl<T> Job<T> {
+    /// Cancels the job unless it already finished. The thread is detached rather than joined, so
+    /// a solver that never checks its [`Ctx`] keeps running in the background until it is done.
+    pub fn cancel(&mut self) {
+        if self.poll().is_none() {
+            self.ctx.cancel();
+            self.handle = None;
+        }
+    }
+
+    pub fn ctx(&self) -> &Ctx {
+        &self.ctx
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.output.is_none() && self.ctx.is_cancelled()
+    }
+
+    pub fn poll(&mut self) -> Option<&T> {
+        if self.output.is_some() {
+            self.output.as_ref()
+        } else if self.handle.as_ref()?.is_finished() {
+            self.output = self.handle.take()?.join().ok();
+            self.output.as_ref()
+        } else {
+            None
+        }
+    }
+}
+
+/// Jobs by key, where starting a job cancels the one it replaces.
+pub struct Jobs<K, T> {
+    jobs: BTreeMap<K, Job<T>>,
+}
+
+impl<K: Ord, T: Send + 'static> Jobs<K, T> {
+    pub fn new() -> Self {
+        Self {
+            jobs: BTreeMap::new(),
+        }
+    }
+
+    pub fn spawn(&mut self, key: K, f: impl FnOnce(Ctx) -> T + Send + 'static) {
+        if let Some(mut job) = self.jobs.insert(key, Job::spawn(f)) {
+            job.cancel();
+        }
+    }
+
+    pub fn cancel(&mut self, key: &K) {
+        if let Some(job) = self.jobs.get_mut(key) {
+            job.cancel();
+        }
+    }
+
+    pub fn remove(&mut self, key: &K) {
+        if let Some(mut job) = self.jobs.remove(key) {
+            job.cancel();
+        }
+    }
+
+    pub fn get(&self, key: &K) -> Option<&Job<T>> {
+        self.jobs.get(key)
+    }
+
+    pub fn get_mut(&mut self, key: &K) -> Option<&mut Job<T>> {
+        self.jobs.get_mut(key)
+    }
+
+    pub fn poll(&mut self, key: &K) -> Option<&T> {
+        self.jobs.get_mut(key)?.poll()
+    }
+
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.jobs.contains_key(key)
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item = &K> {
+        self.jobs.keys()
+    }
+}
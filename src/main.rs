@@ -1,286 +1,173 @@
 use std::{
     collections::BTreeMap,
     fmt::{Display, Formatter},
-    iter, thread,
-    thread::JoinHandle,
-    time::{Duration, Instant},
+    path::PathBuf,
+    process::ExitCode,
+    str::FromStr,
 };
 
-use arboard::Clipboard;
-use crossterm::{
-    event,
-    event::{Event, KeyCode, KeyEvent},
-};
-use itertools::Itertools;
-use ratatui::{
-    Frame,
-    prelude::*,
-    widgets::{Block, BorderType, Padding},
+use anyhow::Context;
+use clap::{Parser, Subcommand};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    answers::Answers,
+    history::{History, Origin, Run},
+    input::Inputs,
+    solver::Registry,
 };
-use throbber_widgets_tui::{Throbber, ThrobberState};
 
+mod answer;
+mod answers;
+mod bench;
 mod day1;
-mod day10;
-mod day11;
-mod day12;
 mod day2;
 mod day3;
 mod day4;
 mod day5;
 mod day6;
 mod day7;
-mod day8;
-mod day9;
-
-const ROWS: usize = 3;
-const COLS: usize = 4;
-
-fn main() {
-    #[rustfmt::skip]
-    let days: Vec<(_, _, fn(_) -> _, fn(_) -> _)> = vec![
-        (1, include_str!("../input/day1.txt"), day1::solve_a, day1::solve_b),
-        (2, include_str!("../input/day2.txt"), day2::solve_a, day2::solve_b),
-        (3, include_str!("../input/day3.txt"), day3::solve_a, day3::solve_b),
-        (4, include_str!("../input/day4.txt"), day4::solve_a, day4::solve_b),
-        (5, include_str!("../input/day5.txt"), day5::solve_a, day5::solve_b),
-        (6, include_str!("../input/day6.txt"), day6::solve_a, day6::solve_b),
-        (7, include_str!("../input/day7.txt"), day7::solve_a, day7::solve_b),
-        // (8, include_str!("../input/day8.txt"), day8::solve_a, day8::solve_b),
-        // (9, include_str!("../input/day9.txt"), day9::solve_a, day9::solve_b),
-        // (10, include_str!("../input/day10.txt"), day10::solve_a, day10::solve_b),
-        // (11, include_str!("../input/day11.txt"), day11::solve_a, day11::solve_b),
-        // (12, include_str!("../input/day12.txt"), day12::solve_a, day12::solve_b),
-    ];
-
-    let mut outputs: BTreeMap<(i32, Part), _> = days
-        .into_iter()
-        .flat_map(|(day, input, solve_a, solve_b)| {
-            [
-                (day, Part::One, input, solve_a),
-                (day, Part::Two, input, solve_b),
-            ]
-        })
-        .map(|(day, part, input, solve)| {
-            (
-                (day, part),
-                Remember::new(thread::spawn(move || {
-                    let start = Instant::now();
-                    (solve(input), start.elapsed())
-                })),
-            )
-        })
-        .collect();
-    let mut state = State::new(outputs.keys());
-
-    let mut clipboard = Clipboard::new().unwrap();
-    let mut terminal = ratatui::init();
-    let tick_rate = Duration::from_millis(100);
-    let mut last_tick = Instant::now();
-    loop {
-        state.throbber_state.calc_next();
-        terminal
-            .draw(|frame| draw(frame, &mut outputs, &state))
-            .unwrap();
-
-        let timeout = tick_rate.saturating_sub(last_tick.elapsed());
-        if event::poll(timeout).unwrap() {
-            if let Event::Key(KeyEvent { code, .. }) = event::read().unwrap() {
-                match code {
-                    KeyCode::Esc | KeyCode::Char('q') => break,
-                    KeyCode::Left | KeyCode::Char('h') => state.move_left(),
-                    KeyCode::Down | KeyCode::Char('j') => state.move_down(),
-                    KeyCode::Up | KeyCode::Char('k') => state.move_up(),
-                    KeyCode::Right | KeyCode::Char('l') => state.move_right(),
-                    KeyCode::Enter => {
-                        if let Some(&output) = outputs
-                            .get_mut(&(state.day, state.part))
-                            .and_then(Remember::poll)
-                        {
-                            clipboard.set_text(output.0.to_string()).unwrap();
-                            state.copy();
-                        }
-                    }
-                    _ => {}
-                }
-            }
-        }
-        if last_tick.elapsed() >= tick_rate {
-            last_tick = Instant::now();
-        }
-    }
-    ratatui::restore();
+mod fetch;
+mod grid;
+mod headless;
+mod history;
+mod input;
+mod intervals;
+mod job;
+mod panic;
+mod solver;
+mod tui;
+mod visual;
+
+/// Solves Advent of Code puzzles in a terminal dashboard.
+#[derive(Debug, Parser)]
+struct Args {
+    #[command(subcommand)]
+    command: Option<Command>,
+    /// Event to run, or to start the dashboard at. Defaults to the latest one.
+    #[arg(long, global = true)]
+    year: Option<i32>,
+    /// Directory containing the `YEAR/dayN.txt` puzzle inputs.
+    #[arg(long, global = true, default_value = "input")]
+    input_dir: PathBuf,
+    /// Read a day's input of the selected year from another file, e.g. `--input 5=trimmed.txt`.
+    #[arg(long = "input", global = true, value_name = "DAY=PATH", value_parser = input::parse_override)]
+    overrides: Vec<(i32, PathBuf)>,
+    /// File recording the accepted answers.
+    #[arg(long, global = true, default_value = "answers.toml")]
+    answers: PathBuf,
+    /// File recording the timings of earlier runs.
+    #[arg(long, global = true, default_value = "history.jsonl")]
+    history: PathBuf,
+    /// How much slower than the previous run a part may get before it counts as a regression.
+    #[arg(long, global = true, value_name = "PERCENT", default_value_t = 10.0)]
+    regression_threshold: f64,
 }
 
-fn draw(
-    frame: &mut Frame,
-    outputs: &mut BTreeMap<(i32, Part), Remember<(u64, Duration)>>,
-    state: &State,
-) {
-    for (row_index, row) in Layout::vertical(iter::repeat_n(Constraint::Length(8), ROWS))
-        .split(frame.area())
-        .iter()
-        .enumerate()
-    {
-        for (col_index, tile) in Layout::horizontal(iter::repeat_n(
-            Constraint::Length(u64::MAX.to_string().len() as u16 + 14),
-            COLS,
-        ))
-        .split(*row)
-        .iter()
-        .enumerate()
-        {
-            let day = (row_index * COLS + col_index + 1) as i32;
-
-            let mut block = Block::bordered()
-                .border_type(BorderType::Rounded)
-                .title_alignment(Alignment::Center)
-                .padding(Padding::proportional(1))
-                .title(format!("[  Day {day}  ]"));
-            block = if state.day == day {
-                block.light_yellow()
-            } else if outputs.contains_key(&(day, Part::One))
-                || outputs.contains_key(&(day, Part::Two))
-            {
-                block.white()
-            } else {
-                block.gray()
-            };
-            let block_inner = block.inner(*tile);
-            frame.render_widget(block, *tile);
-
-            for (part, mut lines) in Layout::vertical([
-                Constraint::Length(1),
-                Constraint::Length(1),
-                Constraint::Length(1),
-                Constraint::Length(1),
-            ])
-            .split(block_inner)
-            .iter()
-            .chunks(2)
-            .into_iter()
-            .enumerate()
-            {
-                let part = Part::try_from(part + 1).unwrap();
-
-                let chunks = Layout::horizontal([
-                    Constraint::Length(8),
-                    Constraint::Length(u64::MAX.to_string().len() as u16),
-                ])
-                .split(*lines.next().unwrap());
-
-                let mut label = match state.copied {
-                    Some((copy_day, copy_part, time))
-                        if copy_day == day
-                            && copy_part == part
-                            && time.elapsed() < Duration::from_secs(1) =>
-                    {
-                        Span::from("Copied! ")
-                    }
-                    _ => Span::from(format!("Part {}: ", part)),
-                };
-                label = if state.day == day && state.part == part {
-                    label.light_yellow()
-                } else if outputs.contains_key(&(day, part)) {
-                    label.white()
-                } else {
-                    label.gray()
-                };
-                frame.render_widget(label, chunks[0]);
-
-                match outputs.get_mut(&(day, part)) {
-                    None => {
-                        let output = Span::from("-").gray().into_right_aligned_line();
-                        frame.render_widget(output, chunks[1]);
-                    }
-                    Some(handle) => match handle.poll() {
-                        None => {
-                            let throbber = Throbber::default()
-                                .throbber_set(throbber_widgets_tui::BRAILLE_SIX_DOUBLE)
-                                .style(Style::new())
-                                .to_line(&state.throbber_state)
-                                .right_aligned();
-                            frame.render_widget(throbber, chunks[1]);
-                        }
-                        Some(output) => {
-                            let output = Span::from(output.0.to_string())
-                                .cyan()
-                                .into_right_aligned_line();
-                            frame.render_widget(output, chunks[1]);
-                        }
-                    },
-                }
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Print answers to stdout instead of starting the dashboard.
+    Run {
+        /// Only run this day.
+        #[arg(long)]
+        day: Option<i32>,
+        /// Only run this part.
+        #[arg(long)]
+        part: Option<Part>,
+    },
+    /// Check answers against the recorded ones, failing if any of them changed.
+    Verify {
+        /// Only verify this day.
+        #[arg(long)]
+        day: Option<i32>,
+        /// Only verify this part.
+        #[arg(long)]
+        part: Option<Part>,
+        /// Record the current answers instead of checking them.
+        #[arg(long)]
+        update: bool,
+    },
+    /// Time each part over a number of runs.
+    Bench(bench::Options),
+    /// Download a day's input, unless it was downloaded before.
+    Fetch(fetch::Options),
+}
 
-                if let Some(&(_, duration)) = outputs.get_mut(&(day, part)).and_then(Remember::poll)
-                {
-                    let time = Span::from(format!("{:.5} s", duration.as_secs_f64()))
-                        .gray()
-                        .into_centered_line();
-                    frame.render_widget(time, *lines.next().unwrap());
-                }
+fn main() -> ExitCode {
+    let args = Args::parse();
+    let events = events();
+    let year = args
+        .year
+        .unwrap_or_else(|| *events.keys().next_back().unwrap());
+    let inputs = Inputs::new(args.input_dir.clone(), year, args.overrides.clone());
+    // Inputs can be downloaded before there are any solutions for their event.
+    if let Some(Command::Fetch(options)) = &args.command {
+        return fetch::run(year, &inputs, options);
+    }
+    let Some(registry) = events.get(&year) else {
+        eprintln!("There are no solutions for {year}");
+        return ExitCode::FAILURE;
+    };
+
+    match args.command {
+        None => match dashboard(&args, &events, year, &inputs) {
+            Ok(()) => ExitCode::SUCCESS,
+            Err(error) => {
+                eprintln!("{error:#}");
+                ExitCode::FAILURE
             }
+        },
+        Some(Command::Run { day, part }) => headless::run(registry, &inputs, day, part),
+        Some(Command::Verify { day, part, update }) => {
+            headless::verify(registry, &inputs, &args.answers, day, part, update)
         }
+        Some(Command::Bench(options)) => bench::run(
+            registry,
+            &inputs,
+            &args.history,
+            args.regression_threshold / 100.0,
+            &options,
+        ),
+        Some(Command::Fetch(_)) => unreachable!("fetching doesn't need solutions"),
     }
 }
 
-#[derive(Debug)]
-struct State {
-    day: i32,
-    part: Part,
-    copied: Option<(i32, Part, Instant)>,
-    throbber_state: ThrobberState,
-}
-
-impl State {
-    pub fn new<'a>(outputs: impl IntoIterator<Item = &'a (i32, Part)>) -> Self {
-        let (day, part) = outputs.into_iter().max().copied().unwrap_or((1, Part::One));
-        Self {
-            day,
-            part,
-            copied: None,
-            throbber_state: ThrobberState::default(),
-        }
-    }
-
-    fn copy(&mut self) {
-        self.copied = Some((self.day, self.part, Instant::now()));
-    }
-
-    fn move_left(&mut self) {
-        self.day = (self.day - 1) / COLS as i32 * COLS as i32
-            + ((self.day - 1) % COLS as i32 - 1).rem_euclid(COLS as i32)
-            + 1;
-    }
-
-    fn move_right(&mut self) {
-        self.day = (self.day - 1) / COLS as i32 * COLS as i32
-            + ((self.day - 1) % COLS as i32 + 1) % COLS as i32
-            + 1;
-    }
-
-    fn move_up(&mut self) {
-        if self.part == Part::Two {
-            self.part = Part::One;
-        } else {
-            self.day = ((self.day - 1) / COLS as i32 - 1).rem_euclid(ROWS as i32) * COLS as i32
-                + (self.day - 1) % COLS as i32
-                + 1;
-            self.part = Part::Two;
-        }
+/// Runs the dashboard and adds the timings it measured to the history.
+fn dashboard(
+    args: &Args,
+    events: &BTreeMap<i32, Registry>,
+    year: i32,
+    inputs: &Inputs,
+) -> anyhow::Result<()> {
+    let answers = Answers::load(&args.answers)?;
+    let history = History::load(&args.history, Origin::Dashboard)?;
+    let threshold = args.regression_threshold / 100.0;
+    let timings = tui::run(events, year, inputs, &answers, &history, threshold)?;
+    if !timings.is_empty() {
+        History::append(&args.history, &Run::new(Origin::Dashboard, timings))?;
     }
+    Ok(())
+}
 
-    fn move_down(&mut self) {
-        if self.part == Part::One {
-            self.part = Part::Two;
-        } else {
-            self.day = ((self.day - 1) / COLS as i32 + 1) % ROWS as i32 * COLS as i32
-                + (self.day - 1) % COLS as i32
-                + 1;
-            self.part = Part::One;
-        }
-    }
+/// The events there are solutions for, by year.
+fn events() -> BTreeMap<i32, Registry> {
+    let mut y2025 = Registry::new(2025);
+    y2025.register(&day1::Day1);
+    y2025.register(&day2::Day2);
+    y2025.register(&day3::Day3);
+    y2025.register(&day4::Day4);
+    y2025.register(&day5::Day5);
+    y2025.register(&day6::Day6);
+    y2025.register(&day7::Day7);
+
+    [y2025]
+        .into_iter()
+        .map(|registry| (registry.year(), registry))
+        .collect()
 }
 
-#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd)]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Deserialize, Serialize)]
+#[serde(into = "usize", try_from = "usize")]
 enum Part {
     One,
     Two,
@@ -298,36 +185,29 @@ impl TryFrom<usize> for Part {
     }
 }
 
-impl Display for Part {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        match self {
-            Part::One => write!(f, "1"),
-            Part::Two => write!(f, "2"),
+impl From<Part> for usize {
+    fn from(part: Part) -> Self {
+        match part {
+            Part::One => 1,
+            Part::Two => 2,
         }
     }
 }
 
-pub struct Remember<T> {
-    handle: Option<JoinHandle<T>>,
-    output: Option<T>,
-}
+impl FromStr for Part {
+    type Err = anyhow::Error;
 
-impl<T> Remember<T> {
-    pub fn new(handle: JoinHandle<T>) -> Self {
-        Self {
-            handle: Some(handle),
-            output: None,
-        }
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let part: usize = s.parse().with_context(|| format!("{s} is not a part"))?;
+        Part::try_from(part).map_err(anyhow::Error::msg)
     }
+}
 
-    pub fn poll(&mut self) -> Option<&T> {
-        if self.output.is_some() {
-            self.output.as_ref()
-        } else if self.handle.as_ref()?.is_finished() {
-            self.output = Some(self.handle.take()?.join().unwrap());
-            self.output.as_ref()
-        } else {
-            None
+impl Display for Part {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Part::One => f.pad("1"),
+            Part::Two => f.pad("2"),
         }
     }
 }
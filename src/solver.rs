@@ -0,0 +1,433 @@
+use std::{
+    any::Any,
+    collections::{BTreeMap, VecDeque},
+    error::Error,
+    fmt::{Display, Formatter},
+    ops::RangeInclusive,
+    sync::{
+        Arc, Mutex, MutexGuard, OnceLock, PoisonError,
+        atomic::{AtomicBool, AtomicU64, Ordering},
+    },
+    time::{Duration, Instant},
+};
+
+use anyhow::{Result, anyhow};
+
+use crate::{
+    Part,
+    answer::Answer,
+    panic,
+    visual::{Frame, Visualise},
+};
+
+/// An example from the puzzle text, along with the answers it should produce.
+#[derive(Debug)]
+pub struct Example {
+    pub input: &'static str,
+    pub part_one: Option<&'static str>,
+    pub part_two: Option<&'static str>,
+}
+
+impl Example {
+    pub fn expected(&self, part: Part) -> Option<&'static str> {
+        match part {
+            Part::One => self.part_one,
+            Part::Two => self.part_two,
+        }
+    }
+}
+
+/// A day of the event. The input is parsed once, after which both parts borrow it.
+pub trait Day: Sync {
+    type Input: Send + Sync + 'static;
+
+    fn day(&self) -> i32;
+
+    fn title(&self) -> &'static str;
+
+    fn examples(&self) -> &'static [Example] {
+        &[]
+    }
+
+    fn parse(&self, input: &str) -> Result<Self::Input>;
+
+    fn solve_a(&self, input: &Self::Input, ctx: &Ctx) -> Result<Answer>;
+
+    fn solve_b(&self, input: &Self::Input, ctx: &Ctx) -> Result<Answer>;
+
+    fn visualisation(&self) -> Option<&dyn Visualise<Self::Input>> {
+        None
+    }
+}
+
+/// A [`Day`] with its input type erased, so that all days can be kept in one [`Registry`].
+pub trait Solver: Sync {
+    fn day(&self) -> i32;
+
+    fn title(&self) -> &'static str;
+
+    fn examples(&self) -> &'static [Example];
+
+    /// The first example with a known answer for `part`.
+    fn example(&self, part: Part) -> Option<&'static Example> {
+        self.examples()
+            .iter()
+            .find(|example| example.expected(part).is_some())
+    }
+
+    fn parse(&self, input: &str) -> Result<Parsed>;
+
+    fn solve(&self, part: Part, input: &Parsed, ctx: &Ctx) -> Result<Answer>;
+
+    /// The frames showing how `part` is solved, if this day can show that.
+    fn visualise(&self, part: Part, input: &Parsed, ctx: &Ctx) -> Option<Result<Vec<Frame>>>;
+}
+
+/// Input as parsed by [`Solver::parse`].
+pub struct Parsed(Box<dyn Any + Send + Sync>);
+
+impl<D: Day> Solver for D {
+    fn day(&self) -> i32 {
+        Day::day(self)
+    }
+
+    fn title(&self) -> &'static str {
+        Day::title(self)
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        Day::examples(self)
+    }
+
+    fn parse(&self, input: &str) -> Result<Parsed> {
+        Ok(Parsed(Box::new(Day::parse(self, input)?)))
+    }
+
+    fn solve(&self, part: Part, input: &Parsed, ctx: &Ctx) -> Result<Answer> {
+        let input = input.downcast()?;
+        match part {
+            Part::One => self.solve_a(input, ctx),
+            Part::Two => self.solve_b(input, ctx),
+        }
+    }
+
+    fn visualise(&self, part: Part, input: &Parsed, ctx: &Ctx) -> Option<Result<Vec<Frame>>> {
+        let visualisation = self.visualisation()?;
+        Some(
+            input
+                .downcast()
+                .and_then(|input| visualisation.frames(part, input, ctx)),
+        )
+    }
+}
+
+impl Parsed {
+    fn downcast<T: 'static>(&self) -> Result<&T> {
+        self.0
+            .downcast_ref()
+            .ok_or_else(|| anyhow!("Input was parsed for another day"))
+    }
+}
+
+/// A puzzle input that is parsed at most once, by whichever part gets to it first.
+pub struct Puzzle {
+    solver: &'static dyn Solver,
+    input: String,
+    parsed: OnceLock<(Result<Parsed, Arc<anyhow::Error>>, Duration)>,
+}
+
+impl Puzzle {
+    pub fn new(solver: &'static dyn Solver, input: impl Into<String>) -> Self {
+        Self {
+            solver,
+            input: input.into(),
+            parsed: OnceLock::new(),
+        }
+    }
+
+    fn parse(&self) -> Result<&Parsed> {
+        let (parsed, _) = self.parsed.get_or_init(|| {
+            let start = Instant::now();
+            let parsed = self.solver.parse(&self.input).map_err(Arc::new);
+            (parsed, start.elapsed())
+        });
+        parsed
+            .as_ref()
+            .map_err(|error| ParseError(error.clone()).into())
+    }
+
+    /// How long parsing took, once it is done.
+    pub fn parse_time(&self) -> Option<Duration> {
+        self.parsed.get().map(|&(_, duration)| duration)
+    }
+
+    pub fn input(&self) -> &str {
+        &self.input
+    }
+
+    pub fn parse_error(&self) -> Option<&anyhow::Error> {
+        self.parsed.get()?.0.as_ref().err().map(|error| &**error)
+    }
+}
+
+/// A parse error, shared by the parts of a [`Puzzle`].
+#[derive(Debug)]
+struct ParseError(Arc<anyhow::Error>);
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl Error for ParseError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.0.source()
+    }
+}
+
+/// Handed to a solver by whoever runs it. Long-running solvers should call [`Ctx::check`] now and
+/// then so they can be cancelled, and can report what they are doing through [`Ctx::log`] and
+/// [`Ctx::value`] rather than printing.
+#[derive(Clone, Debug, Default)]
+pub struct Ctx {
+    cancelled: Arc<AtomicBool>,
+    log: Arc<Mutex<Log>>,
+    progress: Arc<Mutex<Option<(Progress, Instant)>>>,
+}
+
+/// Handle through which a solver reports how far along it is, as handed out by [`Ctx::progress`].
+#[derive(Clone, Debug)]
+pub struct Progress {
+    done: Arc<AtomicU64>,
+    total: u64,
+}
+
+impl Progress {
+    /// Marks `steps` more steps as done. Cheap enough to call in a hot loop.
+    pub fn advance(&self, steps: u64) {
+        self.done.fetch_add(steps, Ordering::Relaxed);
+    }
+
+    pub fn ratio(&self) -> f64 {
+        if self.total == 0 {
+            1.0
+        } else {
+            (self.done.load(Ordering::Relaxed) as f64 / self.total as f64).min(1.0)
+        }
+    }
+}
+
+/// How far along a solver is, as seen by whoever runs it.
+#[derive(Debug)]
+pub struct Status {
+    pub ratio: f64,
+    /// Estimated time left, assuming the remaining steps take as long as the ones before.
+    pub eta: Option<Duration>,
+}
+
+/// What a solver reported while running.
+#[derive(Clone, Debug, Default)]
+pub struct Log {
+    /// The latest [`MAX_LOG_LINES`] lines.
+    pub lines: VecDeque<String>,
+    /// The latest value of everything reported through [`Ctx::value`], in order of first report.
+    pub values: Vec<(String, String)>,
+}
+
+pub const MAX_LOG_LINES: usize = 1000;
+
+impl Ctx {
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+
+    pub fn check(&self) -> Result<()> {
+        if self.is_cancelled() {
+            Err(Cancelled.into())
+        } else {
+            Ok(())
+        }
+    }
+
+    pub fn log(&self, line: impl Display) {
+        let mut log = self.lock();
+        if log.lines.len() == MAX_LOG_LINES {
+            log.lines.pop_front();
+        }
+        log.lines.push_back(line.to_string());
+    }
+
+    /// Reports the current value of something worth keeping an eye on.
+    pub fn value(&self, name: &str, value: impl Display) {
+        let value = value.to_string();
+        let mut log = self.lock();
+        match log.values.iter_mut().find(|(n, _)| n == name) {
+            Some((_, v)) => *v = value,
+            None => log.values.push((name.to_string(), value)),
+        }
+    }
+
+    /// Starts reporting progress towards `total` steps, replacing any progress reported before.
+    pub fn progress(&self, total: u64) -> Progress {
+        let progress = Progress {
+            done: Arc::default(),
+            total,
+        };
+        *self.progress.lock().unwrap_or_else(PoisonError::into_inner) =
+            Some((progress.clone(), Instant::now()));
+        progress
+    }
+
+    /// How far along the solver is, if it reports progress.
+    pub fn status(&self) -> Option<Status> {
+        let progress = self.progress.lock().unwrap_or_else(PoisonError::into_inner);
+        let (progress, start) = progress.as_ref()?;
+        let ratio = progress.ratio();
+        let eta = (ratio > 0.0).then(|| start.elapsed().mul_f64((1.0 - ratio) / ratio));
+        Some(Status { ratio, eta })
+    }
+
+    /// A copy of everything reported so far.
+    pub fn reported(&self) -> Log {
+        self.lock().clone()
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Log> {
+        self.log.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+#[derive(Debug)]
+pub struct Cancelled;
+
+impl Display for Cancelled {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "cancelled")
+    }
+}
+
+impl Error for Cancelled {}
+
+/// How running a solver turned out.
+pub enum Output {
+    Solved(Answer, Duration),
+    Failed(anyhow::Error, Duration),
+    Panicked(String),
+}
+
+impl Output {
+    /// Whether this is a successful answer equal to `expected`, if there is anything to expect.
+    pub fn matches(&self, expected: Option<&str>) -> bool {
+        match self {
+            Output::Solved(answer, _) => {
+                expected.is_none_or(|expected| answer.to_string() == expected)
+            }
+            Output::Failed(..) | Output::Panicked(_) => false,
+        }
+    }
+
+    pub fn duration(&self) -> Option<Duration> {
+        match self {
+            Output::Solved(_, duration) | Output::Failed(_, duration) => Some(*duration),
+            Output::Panicked(_) => None,
+        }
+    }
+}
+
+/// Shows how a part of a puzzle is solved, parsing it first if that hasn't happened yet. Returns
+/// `None` if the day has no visualisation.
+pub fn visualise(puzzle: &Puzzle, part: Part, ctx: &Ctx) -> Option<Result<Vec<Frame>, String>> {
+    let frames = panic::catch(|| {
+        let parsed = match puzzle.parse() {
+            Ok(parsed) => parsed,
+            Err(error) => return Some(Err(error)),
+        };
+        puzzle.solver.visualise(part, parsed, ctx)
+    });
+    match frames {
+        Ok(frames) => frames.map(|frames| frames.map_err(|error| format!("{error:#}"))),
+        Err(message) => Some(Err(format!("panicked: {message}"))),
+    }
+}
+
+/// Runs a part of a puzzle, parsing it first if the other part hasn't already. Only solving is
+/// timed, and any panic is caught.
+pub fn run(puzzle: &Puzzle, part: Part, ctx: &Ctx) -> Output {
+    let solve = || {
+        let parsed = puzzle.parse()?;
+        let start = Instant::now();
+        let answer = puzzle.solver.solve(part, parsed, ctx);
+        anyhow::Ok((answer, start.elapsed()))
+    };
+    match panic::catch(solve) {
+        Ok(Ok((Ok(answer), duration))) => Output::Solved(answer, duration),
+        Ok(Ok((Err(error), duration))) => Output::Failed(error, duration),
+        Ok(Err(error)) => Output::Failed(error, Duration::ZERO),
+        Err(message) => Output::Panicked(message),
+    }
+}
+
+/// Number of days in the event of `year`, if there was one: 25 up to 2024 and 12 since.
+pub fn event_days(year: i32) -> Option<i32> {
+    match year {
+        ..2015 => None,
+        2015..2025 => Some(25),
+        _ => Some(12),
+    }
+}
+
+/// All solvers of an event, indexed by day.
+pub struct Registry {
+    year: i32,
+    days: i32,
+    solvers: BTreeMap<i32, &'static dyn Solver>,
+}
+
+impl Registry {
+    pub fn new(year: i32) -> Self {
+        Self {
+            year,
+            days: event_days(year).unwrap_or_else(|| panic!("there was no event in {year}")),
+            solvers: BTreeMap::new(),
+        }
+    }
+
+    pub fn register(&mut self, solver: &'static dyn Solver) {
+        let day = solver.day();
+        assert!(self.days().contains(&day), "day {day} is not in the event");
+        let previous = self.solvers.insert(day, solver);
+        assert!(previous.is_none(), "day {day} is registered twice");
+    }
+
+    pub fn year(&self) -> i32 {
+        self.year
+    }
+
+    pub fn days(&self) -> RangeInclusive<i32> {
+        1..=self.days
+    }
+
+    pub fn get(&self, day: i32) -> Option<&'static dyn Solver> {
+        self.solvers.get(&day).copied()
+    }
+
+    pub fn solvers(&self) -> impl Iterator<Item = &'static dyn Solver> {
+        self.solvers.values().copied()
+    }
+
+    /// The solver for `day`, or all of them if no day is given.
+    pub fn select(&self, day: Option<i32>) -> Result<Vec<&'static dyn Solver>> {
+        match day {
+            Some(day) => match self.get(day) {
+                Some(solver) => Ok(vec![solver]),
+                None => Err(anyhow!("Day {day} is not implemented")),
+            },
+            None => Ok(self.solvers().collect()),
+        }
+    }
+}
@@ -0,0 +1,1083 @@
+use std::{
+    collections::BTreeMap,
+    iter, ptr,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use anyhow::Result;
+use arboard::Clipboard;
+use crossterm::{
+    event,
+    event::{Event, KeyCode, KeyEvent},
+};
+use itertools::Itertools;
+use ratatui::{
+    DefaultTerminal, Frame,
+    layout::Flex,
+    prelude::*,
+    widgets::{
+        Block, BorderType, Cell, Clear, LineGauge, Padding, Paragraph, Row, Scrollbar,
+        ScrollbarOrientation, ScrollbarState, Table, Wrap,
+    },
+};
+use throbber_widgets_tui::{Throbber, ThrobberState};
+
+use crate::{
+    Part,
+    answers::Answers,
+    bench,
+    history::{History, Timing},
+    input::Inputs,
+    job::{Job, Jobs},
+    panic,
+    solver::{self, Output, Puzzle, Registry, Status},
+    visual,
+};
+
+/// Wide enough for the part label, the pass or fail mark and an answer of [`ANSWER_WIDTH`]
+/// characters.
+const TILE_WIDTH: u16 = 36;
+/// Wide enough for any `u64` answer, with longer ones cut short.
+const ANSWER_WIDTH: u16 = 20;
+/// Room for `✓ ` or `✗ ` before an answer.
+const MARK_WIDTH: u16 = 2;
+const TILE_HEIGHT: u16 = 9;
+
+/// Runs the dashboard until the user quits, starting at `year`, and returns the timings of the real
+/// inputs that were solved. `threshold` is the fraction by which a part may get slower than in the
+/// history before it is shown as a regression.
+pub fn run(
+    events: &BTreeMap<i32, Registry>,
+    year: i32,
+    inputs: &Inputs,
+    answers: &Answers,
+    history: &History,
+    threshold: f64,
+) -> Result<Vec<Timing>> {
+    let mut app = App {
+        events,
+        inputs,
+        answers,
+        history,
+        threshold,
+        jobs: Jobs::new(),
+        puzzles: BTreeMap::new(),
+        errors: BTreeMap::new(),
+    };
+    let mut clipboard = Clipboard::new()?;
+    // Solvers may panic as soon as they start, which must neither end up on the screen nor make
+    // ratatui's hook restore the terminal.
+    let mut terminal = ratatui::init();
+    panic::install_hook();
+
+    app.start_all(year, Source::Input);
+    let mut state = State::new(
+        year,
+        app.days(year),
+        app.jobs.keys().map(|&(_, _, day, part)| (day, part)),
+    );
+    let result = event_loop(&mut terminal, &mut clipboard, &mut app, &mut state);
+    ratatui::restore();
+    result.map(|()| app.timings())
+}
+
+/// A part of a day of an event, run on either the real input or an example.
+type Key = (i32, Source, i32, Part);
+
+struct App<'a> {
+    events: &'a BTreeMap<i32, Registry>,
+    inputs: &'a Inputs,
+    answers: &'a Answers,
+    history: &'a History,
+    threshold: f64,
+    jobs: Jobs<Key, Output>,
+    puzzles: BTreeMap<Key, Arc<Puzzle>>,
+    errors: BTreeMap<(i32, i32), anyhow::Error>,
+}
+
+impl<'a> App<'a> {
+    /// (Re)starts parts of a day, reading its input from disk again. Parts that run on the same
+    /// input share its parsed [`Puzzle`].
+    fn start(&mut self, year: i32, source: Source, day: i32, parts: &[Part]) {
+        let Some(solver) = self
+            .events
+            .get(&year)
+            .and_then(|registry| registry.get(day))
+        else {
+            return;
+        };
+        match source {
+            Source::Input => match self.inputs.load(year, day) {
+                Ok(input) => {
+                    self.errors.remove(&(year, day));
+                    let puzzle = Arc::new(Puzzle::new(solver, input));
+                    for &part in parts {
+                        self.spawn((year, source, day, part), puzzle.clone());
+                    }
+                }
+                Err(error) => {
+                    self.errors.insert((year, day), error);
+                    for &part in parts {
+                        self.remove((year, source, day, part));
+                    }
+                }
+            },
+            Source::Example => {
+                let mut previous: Option<(_, Arc<Puzzle>)> = None;
+                for &part in parts {
+                    let Some(example) = solver.example(part) else {
+                        self.remove((year, source, day, part));
+                        continue;
+                    };
+                    let puzzle = match previous {
+                        Some((previous, puzzle)) if ptr::eq(previous, example) => puzzle,
+                        _ => Arc::new(Puzzle::new(solver, example.input)),
+                    };
+                    self.spawn((year, source, day, part), puzzle.clone());
+                    previous = Some((example, puzzle));
+                }
+            }
+        }
+    }
+
+    /// Starts all parts of an event, unless any of them were started before.
+    fn start_unstarted(&mut self, year: i32, source: Source) {
+        if !self
+            .jobs
+            .keys()
+            .any(|&(y, s, _, _)| (y, s) == (year, source))
+        {
+            self.start_all(year, source);
+        }
+    }
+
+    fn start_all(&mut self, year: i32, source: Source) {
+        for day in 1..=self.days(year) {
+            self.start(year, source, day, &[Part::One, Part::Two]);
+        }
+    }
+
+    fn spawn(&mut self, key: Key, puzzle: Arc<Puzzle>) {
+        let (_, _, _, part) = key;
+        self.puzzles.insert(key, puzzle.clone());
+        self.jobs
+            .spawn(key, move |ctx| solver::run(&puzzle, part, &ctx));
+    }
+
+    fn remove(&mut self, key: Key) {
+        self.puzzles.remove(&key);
+        self.jobs.remove(&key);
+    }
+
+    fn puzzles(&self, year: i32, source: Source, day: i32) -> impl Iterator<Item = &Puzzle> {
+        [Part::One, Part::Two]
+            .into_iter()
+            .filter_map(move |part| self.puzzles.get(&(year, source, day, part)))
+            .map(|puzzle| &**puzzle)
+    }
+
+    /// Why a day's input could not be read or parsed.
+    fn error(&self, year: i32, source: Source, day: i32) -> Option<&anyhow::Error> {
+        let error = match source {
+            Source::Input => self.errors.get(&(year, day)),
+            Source::Example => None,
+        };
+        error.or_else(|| {
+            self.puzzles(year, source, day)
+                .find_map(Puzzle::parse_error)
+        })
+    }
+
+    fn parse_time(&self, year: i32, source: Source, day: i32) -> Option<Duration> {
+        self.puzzles(year, source, day).find_map(Puzzle::parse_time)
+    }
+
+    /// The answer a part should produce, if known.
+    fn expected(&self, (year, source, day, part): Key) -> Option<&'a str> {
+        match source {
+            Source::Input => self.answers.get(year, day, part),
+            Source::Example => self
+                .events
+                .get(&year)?
+                .get(day)?
+                .example(part)?
+                .expected(part),
+        }
+    }
+
+    fn registry(&self, year: i32) -> Option<&'a Registry> {
+        self.events.get(&year)
+    }
+
+    /// Number of days in the event of `year`.
+    fn days(&self, year: i32) -> i32 {
+        self.registry(year)
+            .map_or(0, |registry| *registry.days().end())
+    }
+
+    /// Timings of the real inputs that finished, in order.
+    fn timings(&mut self) -> Vec<Timing> {
+        let keys: Vec<_> = self.jobs.keys().copied().collect();
+        keys.into_iter()
+            .filter(|&(_, source, _, _)| source == Source::Input)
+            .filter_map(|key @ (year, _, day, part)| match self.jobs.poll(&key)? {
+                Output::Solved(_, duration) => Some(Timing {
+                    year,
+                    day,
+                    part,
+                    duration: *duration,
+                }),
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd)]
+enum Source {
+    Input,
+    Example,
+}
+
+fn event_loop(
+    terminal: &mut DefaultTerminal,
+    clipboard: &mut Clipboard,
+    app: &mut App,
+    state: &mut State,
+) -> Result<()> {
+    let tick_rate = Duration::from_millis(100);
+    let mut last_tick = Instant::now();
+    loop {
+        state.throbber_state.calc_next();
+        terminal.draw(|frame| draw(frame, app, state))?;
+
+        let timeout = tick_rate.saturating_sub(last_tick.elapsed());
+        if event::poll(timeout)?
+            && let Event::Key(KeyEvent { code, .. }) = event::read()?
+        {
+            if let Some(player) = &mut state.player {
+                if !player.handle(code) {
+                    state.player = None;
+                }
+                continue;
+            }
+            match code {
+                KeyCode::Esc if state.popup => state.popup = false,
+                KeyCode::Esc if state.detail => state.detail = false,
+                KeyCode::Esc if state.timings => state.timings = false,
+                KeyCode::Esc | KeyCode::Char('q') => return Ok(()),
+                KeyCode::Char('e') => state.popup = !state.popup,
+                KeyCode::Char('d') => state.detail = !state.detail,
+                KeyCode::Char('t') => state.timings = !state.timings,
+                KeyCode::Char('v') => {
+                    if let Some(puzzle) = app.puzzles.get(&state.key()) {
+                        state.player = Some(Player::new(state.key(), puzzle.clone()));
+                    }
+                }
+                KeyCode::Char('c') => app.jobs.cancel(&state.key()),
+                KeyCode::Char('r') => app.start(state.year, state.source, state.day, &[state.part]),
+                KeyCode::Char('R') => app.start_all(state.year, state.source),
+                KeyCode::Char('x') => {
+                    state.source = match state.source {
+                        Source::Input => Source::Example,
+                        Source::Example => Source::Input,
+                    };
+                    app.start_unstarted(state.year, state.source);
+                }
+                KeyCode::Char('y') => {
+                    let year = app
+                        .events
+                        .keys()
+                        .find(|&&year| year > state.year)
+                        .or(app.events.keys().next());
+                    if let Some(&year) = year {
+                        state.switch_year(year, app.days(year));
+                        app.start_unstarted(state.year, state.source);
+                    }
+                }
+                KeyCode::Left | KeyCode::Char('h') => state.move_left(),
+                KeyCode::Down | KeyCode::Char('j') => state.move_down(),
+                KeyCode::Up | KeyCode::Char('k') => state.move_up(),
+                KeyCode::Right | KeyCode::Char('l') => state.move_right(),
+                KeyCode::Enter => {
+                    if let Some(Output::Solved(answer, _)) = app.jobs.poll(&state.key()) {
+                        clipboard.set_text(answer.to_string())?;
+                        state.copy();
+                    }
+                }
+                _ => {}
+            }
+        }
+        if last_tick.elapsed() >= tick_rate {
+            last_tick = Instant::now();
+            if let Some(player) = &mut state.player {
+                player.tick();
+            }
+        }
+    }
+}
+
+fn draw(frame: &mut Frame, app: &mut App, state: &mut State) {
+    let [grid, help] =
+        Layout::vertical([Constraint::Fill(1), Constraint::Length(1)]).areas(frame.area());
+    state.fit(grid);
+
+    for (row_index, row) in Layout::vertical(iter::repeat_n(
+        Constraint::Length(TILE_HEIGHT),
+        state.rows as usize,
+    ))
+    .split(grid)
+    .iter()
+    .enumerate()
+    {
+        for (col_index, tile) in Layout::horizontal(iter::repeat_n(
+            Constraint::Length(TILE_WIDTH),
+            state.cols as usize,
+        ))
+        .split(*row)
+        .iter()
+        .enumerate()
+        {
+            let day = (state.scroll + row_index as i32) * state.cols + col_index as i32 + 1;
+            if day > state.days {
+                break;
+            }
+            let (year, source) = (state.year, state.source);
+
+            let mut block = Block::bordered()
+                .border_type(BorderType::Rounded)
+                .title_alignment(Alignment::Center)
+                .padding(Padding::proportional(1))
+                .title(format!("[  Day {day}  ]"));
+            block = block.title_bottom(
+                Line::from(
+                    app.registry(year)
+                        .and_then(|registry| registry.get(day))
+                        .map_or("not implemented", |solver| solver.title()),
+                )
+                .gray()
+                .centered(),
+            );
+            let failed = app.error(year, source, day).is_some()
+                || [Part::One, Part::Two].into_iter().any(|part| {
+                    let expected = app.expected((year, source, day, part));
+                    app.jobs
+                        .poll(&(year, source, day, part))
+                        .is_some_and(|output| !output.matches(expected))
+                });
+            block = if state.day == day {
+                block.light_yellow()
+            } else if failed {
+                block.red()
+            } else if app.jobs.contains_key(&(year, source, day, Part::One))
+                || app.jobs.contains_key(&(year, source, day, Part::Two))
+            {
+                block.white()
+            } else {
+                block.gray()
+            };
+            let block_inner = block.inner(*tile);
+            frame.render_widget(block, *tile);
+
+            if let Some(error) = app.error(year, source, day) {
+                let message = Paragraph::new(format!("{error:#}"))
+                    .red()
+                    .wrap(Wrap { trim: true });
+                frame.render_widget(message, block_inner);
+                continue;
+            }
+
+            let [parse, parts] =
+                Layout::vertical([Constraint::Length(1), Constraint::Length(4)]).areas(block_inner);
+            if let Some(duration) = app.parse_time(year, source, day) {
+                let time = Line::from(format!("parsed in {:.5} s", duration.as_secs_f64()))
+                    .gray()
+                    .centered();
+                frame.render_widget(time, parse);
+            }
+
+            for (part, mut lines) in Layout::vertical([Constraint::Length(1); 4])
+                .split(parts)
+                .iter()
+                .chunks(2)
+                .into_iter()
+                .enumerate()
+            {
+                let part = Part::try_from(part + 1).unwrap();
+
+                let chunks = Layout::horizontal([
+                    Constraint::Length(8),
+                    Constraint::Length(MARK_WIDTH + ANSWER_WIDTH),
+                ])
+                .split(*lines.next().unwrap());
+
+                let mut label = match state.copied {
+                    Some((copy_day, copy_part, time))
+                        if copy_day == day
+                            && copy_part == part
+                            && time.elapsed() < Duration::from_secs(1) =>
+                    {
+                        Span::from("Copied! ")
+                    }
+                    _ => Span::from(format!("Part {}: ", part)),
+                };
+                label = if state.day == day && state.part == part {
+                    label.light_yellow()
+                } else if app.jobs.contains_key(&(year, source, day, part)) {
+                    label.white()
+                } else {
+                    label.gray()
+                };
+                frame.render_widget(label, chunks[0]);
+
+                let expected = app.expected((year, source, day, part));
+                match app.jobs.get_mut(&(year, source, day, part)) {
+                    None => {
+                        let output = Span::from("-").gray().into_right_aligned_line();
+                        frame.render_widget(output, chunks[1]);
+                    }
+                    Some(job) if job.is_cancelled() => {
+                        let output = Span::from("cancelled").gray().into_right_aligned_line();
+                        frame.render_widget(output, chunks[1]);
+                    }
+                    Some(job) => match (job.ctx().status(), job.poll()) {
+                        (Some(status), None) => {
+                            let label = match status.eta {
+                                Some(eta) => format!(
+                                    "{:>3.0}% {:>4.0}s ",
+                                    status.ratio * 100.0,
+                                    eta.as_secs_f64().ceil()
+                                ),
+                                None => format!("{:>3.0}% ", status.ratio * 100.0),
+                            };
+                            let gauge = LineGauge::default()
+                                .ratio(status.ratio)
+                                .label(label)
+                                .filled_style(Style::new().cyan())
+                                .unfilled_style(Style::new().dark_gray());
+                            frame.render_widget(gauge, chunks[1]);
+                        }
+                        (None, None) => {
+                            let throbber = Throbber::default()
+                                .throbber_set(throbber_widgets_tui::BRAILLE_SIX_DOUBLE)
+                                .style(Style::new())
+                                .to_line(&state.throbber_state)
+                                .right_aligned();
+                            frame.render_widget(throbber, chunks[1]);
+                        }
+                        (_, Some(output @ Output::Solved(answer, _))) => {
+                            let summary = answer.summary_within(ANSWER_WIDTH.into());
+                            let (mark, summary) = match expected {
+                                None => (Span::default(), Span::from(summary).cyan()),
+                                Some(_) if output.matches(expected) => {
+                                    (Span::from("✓").green(), Span::from(summary).green())
+                                }
+                                Some(_) => (Span::from("✗").red(), Span::from(summary).red()),
+                            };
+                            // The mark gets a cell of its own, just left of the answer, so a long
+                            // answer can't push it out.
+                            let [_, mark_area, summary_area] = Layout::horizontal([
+                                Constraint::Fill(1),
+                                Constraint::Length(MARK_WIDTH),
+                                Constraint::Length(summary.width() as u16),
+                            ])
+                            .areas(chunks[1]);
+                            frame.render_widget(mark, mark_area);
+                            frame.render_widget(summary, summary_area);
+                        }
+                        (_, Some(Output::Failed(error, _))) => {
+                            let output =
+                                Span::from(error.to_string()).red().into_left_aligned_line();
+                            frame.render_widget(output, chunks[1]);
+                        }
+                        (_, Some(Output::Panicked(_))) => {
+                            let output = Span::from("panicked").red().into_right_aligned_line();
+                            frame.render_widget(output, chunks[1]);
+                        }
+                    },
+                }
+
+                if let Some(duration) = app
+                    .jobs
+                    .poll(&(year, source, day, part))
+                    .and_then(Output::duration)
+                {
+                    let regressed = source == Source::Input
+                        && app
+                            .history
+                            .compare(year, day, part, duration, app.threshold)
+                            .regressed;
+                    let time = Span::from(format!("{:.5} s", duration.as_secs_f64()));
+                    let time = if regressed { time.red() } else { time.gray() };
+                    let time = time.into_centered_line();
+                    frame.render_widget(time, *lines.next().unwrap());
+                }
+            }
+        }
+    }
+
+    let hidden = state.total_rows() - state.rows;
+    if hidden > 0 {
+        let mut scrollbar_state =
+            ScrollbarState::new(hidden as usize + 1).position(state.scroll as usize);
+        frame.render_stateful_widget(
+            Scrollbar::new(ScrollbarOrientation::VerticalRight),
+            grid,
+            &mut scrollbar_state,
+        );
+    }
+
+    let keys = match state.source {
+        Source::Input => "x examples",
+        Source::Example => "x real input",
+    };
+    let keys = format!(
+        "q quit · ←↓↑→ move · enter copy · d details · v visualise · e error · t timings · c cancel · r rerun · R rerun all · {keys} · y year"
+    );
+    let help = Layout::horizontal([Constraint::Length(6), Constraint::Fill(1)]).split(help);
+    frame.render_widget(Span::from(state.year.to_string()).bold(), help[0]);
+    frame.render_widget(Line::from(keys).dark_gray(), help[1]);
+
+    if state.timings {
+        draw_timings(frame, app);
+    }
+
+    if state.detail {
+        draw_detail(frame, app, state);
+    }
+
+    if let Some(player) = &mut state.player {
+        draw_player(frame, player);
+    }
+
+    if state.popup
+        && let Some(message) = problem(app, state.key())
+    {
+        let [area] = Layout::vertical([Constraint::Percentage(50)])
+            .flex(Flex::Center)
+            .areas(frame.area());
+        let [area] = Layout::horizontal([Constraint::Percentage(60)])
+            .flex(Flex::Center)
+            .areas(area);
+        let block = Block::bordered()
+            .border_type(BorderType::Rounded)
+            .title_alignment(Alignment::Center)
+            .padding(Padding::proportional(1))
+            .title(format!("[  Day {} part {}  ]", state.day, state.part))
+            .red();
+        let message = Paragraph::new(message)
+            .block(block)
+            .wrap(Wrap { trim: false });
+        frame.render_widget(Clear, area);
+        frame.render_widget(message, area);
+    }
+}
+
+/// What went wrong with a part, if anything.
+fn problem(app: &mut App, key @ (year, source, day, _): Key) -> Option<String> {
+    let expected = app.expected(key);
+    match app.jobs.poll(&key) {
+        Some(Output::Failed(error, _)) => Some(chain(error)),
+        Some(Output::Panicked(message)) => Some(format!("panicked: {message}")),
+        Some(output @ Output::Solved(answer, _)) if !output.matches(expected) => Some(format!(
+            "expected {}, got {answer}",
+            expected.unwrap_or_default()
+        )),
+        _ => app.error(year, source, day).map(chain),
+    }
+}
+
+/// Everything known about the selected part, covering the whole screen.
+fn draw_detail(frame: &mut Frame, app: &mut App, state: &State) {
+    let key @ (year, source, day, part) = state.key();
+    let title = app
+        .registry(year)
+        .and_then(|registry| registry.get(day))
+        .map_or("not implemented", |solver| solver.title());
+    let block = Block::bordered()
+        .border_type(BorderType::Rounded)
+        .title_alignment(Alignment::Center)
+        .title(format!("[  {year} day {day} part {part}: {title}  ]"))
+        .title_bottom(Line::from("esc close").dark_gray());
+    let area = frame.area();
+    frame.render_widget(Clear, area);
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let [top, input] =
+        Layout::vertical([Constraint::Fill(1), Constraint::Percentage(30)]).areas(inner);
+    let [summary, log] =
+        Layout::horizontal([Constraint::Percentage(40), Constraint::Fill(1)]).areas(top);
+    let section = |title: &'static str| {
+        Block::bordered()
+            .border_type(BorderType::Rounded)
+            .padding(Padding::horizontal(1))
+            .title(title)
+    };
+
+    let reported = app
+        .jobs
+        .get(&key)
+        .map(|job| job.ctx().reported())
+        .unwrap_or_default();
+    let parse_time = app.parse_time(year, source, day);
+    let expected = app.expected(key);
+    let problem = problem(app, key);
+    let cancelled = app.jobs.get(&key).is_some_and(|job| job.is_cancelled());
+    let started = app.jobs.contains_key(&key);
+    let status = app.jobs.get(&key).and_then(|job| job.ctx().status());
+    let output = app.jobs.poll(&key);
+
+    let mut lines = vec![match output {
+        _ if cancelled => Line::from("cancelled").gray(),
+        None if started => match status {
+            Some(Status {
+                ratio,
+                eta: Some(eta),
+            }) => Line::from(format!(
+                "running: {:.0}%, about {} left",
+                ratio * 100.0,
+                bench::format(eta)
+            )),
+            Some(Status { ratio, eta: None }) => {
+                Line::from(format!("running: {:.0}%", ratio * 100.0))
+            }
+            None => Line::from("running"),
+        },
+        None => Line::from("not started").gray(),
+        Some(Output::Solved(..)) => Line::from("solved").green(),
+        Some(Output::Failed(..)) => Line::from("failed").red(),
+        Some(Output::Panicked(_)) => Line::from("panicked").red(),
+    }];
+    if let Some(Output::Solved(answer, _)) = output {
+        lines.extend(Text::from(answer.to_string()).bold().lines);
+    }
+    if let Some(expected) = expected {
+        lines.push(Line::from(format!("expected {expected}")).gray());
+    }
+    lines.push(Line::default());
+    if let Some(duration) = parse_time {
+        lines.push(Line::from(format!("parse: {}", bench::format(duration))));
+    }
+    if let Some(duration) = output.and_then(Output::duration) {
+        lines.push(Line::from(format!("solve: {}", bench::format(duration))));
+    }
+    if !reported.values.is_empty() {
+        lines.push(Line::default());
+        lines.extend(
+            reported
+                .values
+                .iter()
+                .map(|(name, value)| Line::from(format!("{name} = {value}")).cyan()),
+        );
+    }
+    if let Some(problem) = problem {
+        lines.push(Line::default());
+        lines.extend(Text::from(problem).red().lines);
+    }
+    let summary_block = section("Summary");
+    frame.render_widget(
+        Paragraph::new(lines)
+            .block(summary_block)
+            .wrap(Wrap { trim: false }),
+        summary,
+    );
+
+    // Keep the latest lines in view.
+    let log_block = section("Log");
+    let height = log_block.inner(log).height as usize;
+    let skip = reported.lines.len().saturating_sub(height);
+    let lines: Vec<_> = reported
+        .lines
+        .iter()
+        .skip(skip)
+        .map(|line| Line::from(line.as_str()))
+        .collect();
+    frame.render_widget(Paragraph::new(lines).block(log_block), log);
+
+    let input_block = section("Input");
+    let height = input_block.inner(input).height as usize;
+    let preview = app
+        .puzzles
+        .get(&key)
+        .map(|puzzle| puzzle.input().lines().take(height).join("\n"))
+        .unwrap_or_default();
+    frame.render_widget(Paragraph::new(preview).gray().block(input_block), input);
+}
+
+/// Plays the frames of a part's visualisation, covering the whole screen.
+fn draw_player(frame: &mut Frame, player: &mut Player) {
+    let (year, _, day, part) = player.key;
+    let block = Block::bordered()
+        .border_type(BorderType::Rounded)
+        .title_alignment(Alignment::Center)
+        .title(format!("[  {year} day {day} part {part}: visualisation  ]"))
+        .title_bottom(
+            Line::from("space play/pause · ←→ step · g/G first/last · esc close").dark_gray(),
+        );
+    let area = frame.area();
+    frame.render_widget(Clear, area);
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let (index, playing) = (player.index, player.playing);
+    let frames = match player.job.poll() {
+        None => {
+            frame.render_widget(Line::from("drawing frames…").gray().centered(), inner);
+            return;
+        }
+        Some(None) => {
+            let message = format!("Day {day} has no visualisation");
+            frame.render_widget(Line::from(message).gray().centered(), inner);
+            return;
+        }
+        Some(Some(Err(error))) => {
+            let message = Paragraph::new(error.as_str())
+                .red()
+                .wrap(Wrap { trim: false });
+            frame.render_widget(message, inner);
+            return;
+        }
+        Some(Some(Ok(frames))) if frames.is_empty() => {
+            frame.render_widget(Line::from("no frames").gray().centered(), inner);
+            return;
+        }
+        Some(Some(Ok(frames))) => frames,
+    };
+    let current = &frames[index];
+
+    let [caption, grid, footer] = Layout::vertical([
+        Constraint::Length(1),
+        Constraint::Fill(1),
+        Constraint::Length(1),
+    ])
+    .areas(inner);
+    frame.render_widget(
+        Line::from(current.caption.as_str()).bold().centered(),
+        caption,
+    );
+    let status = if playing { "playing" } else { "paused" };
+    let position = format!("frame {}/{} · {status}", index + 1, frames.len());
+    frame.render_widget(Line::from(position).gray().centered(), footer);
+
+    // Scroll so the first highlighted cell is in the middle, if the grid doesn't fit.
+    let (rows, cols) = (grid.height as usize, grid.width as usize);
+    let (focus_row, focus_col) = current.highlights.first().copied().unwrap_or_default();
+    let (height, width) = (current.grid.height(), current.grid.width());
+    let top = focus_row
+        .saturating_sub(rows / 2)
+        .min(height.saturating_sub(rows));
+    let left = focus_col
+        .saturating_sub(cols / 2)
+        .min(width.saturating_sub(cols));
+    let lines: Vec<_> = current
+        .grid
+        .rows()
+        .enumerate()
+        .skip(top)
+        .take(rows)
+        .map(|(y, row)| {
+            row.iter()
+                .enumerate()
+                .skip(left)
+                .take(cols)
+                .map(|(x, &c)| {
+                    let span = Span::from(c.to_string());
+                    if current.highlights.contains(&(y, x)) {
+                        span.light_yellow().bold()
+                    } else if c == '.' {
+                        span.dark_gray()
+                    } else {
+                        span.white()
+                    }
+                })
+                .collect::<Line>()
+        })
+        .collect();
+    let [grid] = Layout::horizontal([Constraint::Length(width.min(cols) as u16)])
+        .flex(Flex::Center)
+        .areas(grid);
+    frame.render_widget(Paragraph::new(lines), grid);
+}
+
+/// Lists the timings of the real inputs next to the ones in the history.
+fn draw_timings(frame: &mut Frame, app: &mut App) {
+    let header = Row::new(["", "current", "previous", "best", "change"]).bold();
+    let rows = app.timings().into_iter().map(|timing| {
+        let comparison = app.history.compare(
+            timing.year,
+            timing.day,
+            timing.part,
+            timing.duration,
+            app.threshold,
+        );
+        let change = comparison
+            .change(timing.duration)
+            .map_or("-".to_string(), |change| format!("{:+.1}%", change * 100.0));
+        let row = Row::new([
+            Cell::from(format!(
+                "{} day {} part {}",
+                timing.year, timing.day, timing.part
+            )),
+            Cell::from(bench::format(timing.duration)),
+            Cell::from(comparison.previous.map_or("-".to_string(), bench::format)),
+            Cell::from(comparison.best.map_or("-".to_string(), bench::format)),
+            Cell::from(Line::from(change).right_aligned()),
+        ]);
+        if comparison.regressed { row.red() } else { row }
+    });
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(19),
+            Constraint::Length(11),
+            Constraint::Length(11),
+            Constraint::Length(11),
+            Constraint::Length(8),
+        ],
+    )
+    .header(header)
+    .block(
+        Block::bordered()
+            .border_type(BorderType::Rounded)
+            .title_alignment(Alignment::Center)
+            .padding(Padding::proportional(1))
+            .title("[  Timings  ]"),
+    );
+
+    let [area] = Layout::vertical([Constraint::Percentage(70)])
+        .flex(Flex::Center)
+        .areas(frame.area());
+    let [area] = Layout::horizontal([Constraint::Length(75)])
+        .flex(Flex::Center)
+        .areas(area);
+    frame.render_widget(Clear, area);
+    frame.render_widget(table, area);
+}
+
+fn chain(error: &anyhow::Error) -> String {
+    error.chain().map(ToString::to_string).join("\ncaused by: ")
+}
+
+struct State {
+    year: i32,
+    day: i32,
+    part: Part,
+    /// Number of days in the event.
+    days: i32,
+    /// Number of columns in the grid, and of rows that fit on screen, as of the last draw.
+    cols: i32,
+    rows: i32,
+    /// The first row on screen.
+    scroll: i32,
+    copied: Option<(i32, Part, Instant)>,
+    popup: bool,
+    detail: bool,
+    timings: bool,
+    player: Option<Player>,
+    source: Source,
+    throbber_state: ThrobberState,
+}
+
+impl State {
+    pub fn new(year: i32, days: i32, outputs: impl IntoIterator<Item = (i32, Part)>) -> Self {
+        let (day, part) = outputs.into_iter().max().unwrap_or((1, Part::One));
+        Self {
+            year,
+            day,
+            part,
+            days,
+            cols: 1,
+            rows: 1,
+            scroll: 0,
+            copied: None,
+            popup: false,
+            detail: false,
+            timings: false,
+            player: None,
+            source: Source::Input,
+            throbber_state: ThrobberState::default(),
+        }
+    }
+
+    fn key(&self) -> Key {
+        (self.year, self.source, self.day, self.part)
+    }
+
+    fn switch_year(&mut self, year: i32, days: i32) {
+        self.year = year;
+        self.days = days;
+        self.day = self.day.min(self.days);
+    }
+
+    fn copy(&mut self) {
+        self.copied = Some((self.day, self.part, Instant::now()));
+    }
+
+    /// Lays the grid out in `area`, scrolling as little as needed to keep the selected day in
+    /// view.
+    fn fit(&mut self, area: Rect) {
+        self.cols = i32::from(area.width / TILE_WIDTH).clamp(1, self.days);
+        self.rows = i32::from(area.height / TILE_HEIGHT).clamp(1, self.total_rows());
+        let row = self.row();
+        self.scroll = self
+            .scroll
+            .clamp(row - self.rows + 1, row)
+            .min(self.total_rows() - self.rows);
+    }
+
+    fn total_rows(&self) -> i32 {
+        (self.days - 1) / self.cols + 1
+    }
+
+    fn row(&self) -> i32 {
+        (self.day - 1) / self.cols
+    }
+
+    fn col(&self) -> i32 {
+        (self.day - 1) % self.cols
+    }
+
+    /// Number of days on a row, as the last one may not be full.
+    fn row_len(&self, row: i32) -> i32 {
+        (self.days - row * self.cols).min(self.cols)
+    }
+
+    /// Selects the day at `row` and `col`, or the last one on that row if it is shorter.
+    fn select(&mut self, row: i32, col: i32) {
+        self.day = row * self.cols + col.min(self.row_len(row) - 1) + 1;
+    }
+
+    fn move_left(&mut self) {
+        let row = self.row();
+        self.select(row, (self.col() - 1).rem_euclid(self.row_len(row)));
+    }
+
+    fn move_right(&mut self) {
+        let row = self.row();
+        self.select(row, (self.col() + 1) % self.row_len(row));
+    }
+
+    fn move_up(&mut self) {
+        if self.part == Part::Two {
+            self.part = Part::One;
+        } else {
+            self.select((self.row() - 1).rem_euclid(self.total_rows()), self.col());
+            self.part = Part::Two;
+        }
+    }
+
+    fn move_down(&mut self) {
+        if self.part == Part::One {
+            self.part = Part::Two;
+        } else {
+            self.select((self.row() + 1) % self.total_rows(), self.col());
+            self.part = Part::One;
+        }
+    }
+}
+
+/// Steps through the frames of a part's visualisation, which are drawn on their own thread.
+struct Player {
+    key: Key,
+    job: Job<Option<Result<Vec<visual::Frame>, String>>>,
+    index: usize,
+    playing: bool,
+}
+
+impl Player {
+    fn new(key @ (_, _, _, part): Key, puzzle: Arc<Puzzle>) -> Self {
+        Self {
+            key,
+            job: Job::spawn(move |ctx| solver::visualise(&puzzle, part, &ctx)),
+            index: 0,
+            playing: true,
+        }
+    }
+
+    fn len(&mut self) -> usize {
+        match self.job.poll() {
+            Some(Some(Ok(frames))) => frames.len(),
+            _ => 0,
+        }
+    }
+
+    /// Moves on to the next frame while playing, pausing at the last one.
+    fn tick(&mut self) {
+        if self.playing && self.len() > 0 {
+            if self.index + 1 < self.len() {
+                self.index += 1;
+            } else {
+                self.playing = false;
+            }
+        }
+    }
+
+    /// Handles a key, returning whether the player stays open.
+    fn handle(&mut self, code: KeyCode) -> bool {
+        let last = self.len().saturating_sub(1);
+        match code {
+            KeyCode::Esc | KeyCode::Char('q' | 'v') => return false,
+            KeyCode::Char(' ') => {
+                if self.index == last {
+                    self.index = 0;
+                }
+                self.playing = !self.playing;
+            }
+            KeyCode::Left | KeyCode::Char('h') => {
+                self.playing = false;
+                self.index = self.index.saturating_sub(1);
+            }
+            KeyCode::Right | KeyCode::Char('l') => {
+                self.playing = false;
+                self.index = (self.index + 1).min(last);
+            }
+            KeyCode::Home | KeyCode::Char('g') => self.index = 0,
+            KeyCode::End | KeyCode::Char('G') => self.index = last,
+            _ => {}
+        }
+        true
+    }
+}
+
+impl Drop for Player {
+    fn drop(&mut self) {
+        self.job.cancel();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ratatui::layout::Rect;
+
+    use super::State;
+    use crate::Part;
+
+    #[test]
+    fn test_layout_of_25_days() {
+        let mut state = State::new(2024, 25, []);
+        state.fit(Rect::new(0, 0, 150, 40));
+        assert_eq!((state.cols, state.rows, state.total_rows()), (4, 4, 7));
+        assert_eq!(state.row_len(5), 4);
+        assert_eq!(state.row_len(6), 1);
+
+        // Going down from a column the short last row doesn't reach lands on its only day.
+        state.day = 23;
+        state.part = Part::Two;
+        state.move_down();
+        assert_eq!((state.day, state.part), (25, Part::One));
+        state.fit(Rect::new(0, 0, 150, 40));
+        assert_eq!(state.scroll, 3);
+        state.move_right();
+        assert_eq!(state.day, 25);
+
+        state.part = Part::Two;
+        state.move_down();
+        assert_eq!((state.day, state.part), (1, Part::One));
+        state.fit(Rect::new(0, 0, 150, 40));
+        assert_eq!(state.scroll, 0);
+        state.move_up();
+        assert_eq!((state.day, state.part), (25, Part::Two));
+    }
+}
@@ -0,0 +1,23 @@
+use std::collections::BTreeSet;
+
+use anyhow::Result;
+
+use crate::{
+    Part,
+    grid::{Grid, Pos},
+    solver::Ctx,
+};
+
+/// A picture of a puzzle at some point while it is being solved.
+#[derive(Clone, Debug)]
+pub struct Frame {
+    pub grid: Grid<char>,
+    /// Cells to draw attention to.
+    pub highlights: BTreeSet<Pos>,
+    pub caption: String,
+}
+
+/// Implemented by days that can show how a part is solved, step by step.
+pub trait Visualise<I> {
+    fn frames(&self, part: Part, input: &I, ctx: &Ctx) -> Result<Vec<Frame>>;
+}